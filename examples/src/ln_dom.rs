@@ -25,13 +25,8 @@ struct DOMTimeline {
   current_frame: u64,
   #[yaserde(attribute = true)]
   guides: u64,
-  layers: Layers,
-}
-
-#[derive(YaDeserialize, Debug, PartialEq)]
-struct Layers {
-  #[yaserde(rename = "DOMLayer")]
-  dom_layer: Vec<DOMLayer>,
+  #[yaserde(rename = "DOMLayer", wrapper = "layers")]
+  layers: Vec<DOMLayer>,
 }
 
 #[derive(YaDeserialize, Debug, PartialEq)]
@@ -62,18 +57,16 @@ fn parsing_ln_dom() {
         named: "dagger timeline name".to_string(),
         current_frame: 7,
         guides: 11,
-        layers: Layers {
-          dom_layer: vec![
-            DOMLayer {
-              named: "Layer 2".to_string(),
-              name2: "Lalayer 2".to_string(),
-            },
-            DOMLayer {
-              named: "Layer 1".to_string(),
-              name2: "Lalayer 1".to_string(),
-            },
-          ],
-        },
+        layers: vec![
+          DOMLayer {
+            named: "Layer 2".to_string(),
+            name2: "Lalayer 2".to_string(),
+          },
+          DOMLayer {
+            named: "Layer 1".to_string(),
+            name2: "Lalayer 1".to_string(),
+          },
+        ],
       },
     },
   };
@@ -0,0 +1,151 @@
+//! `xs:base64Binary` and `xs:hexBinary` encodings for byte buffers, used by fields with the
+//! `base64` and `hex` attributes.
+//!
+//! ```
+//! use yaserde::binary::{decode_base64, encode_base64, encode_hex};
+//!
+//! assert_eq!(encode_base64(b"yaserde", None), "eWFzZXJkZQ==");
+//! assert_eq!(decode_base64("eWFz\n  ZXJkZQ==").unwrap(), b"yaserde");
+//! assert_eq!(encode_hex(&[0x0f, 0xa0]), "0FA0");
+//! ```
+
+const BASE64_ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode `bytes` in base64, with lines of at most `line_length` characters when given.
+pub fn encode_base64(bytes: &[u8], line_length: Option<usize>) -> String {
+  let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+  for chunk in bytes.chunks(3) {
+    let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| {
+      group | (u32::from(*byte) << (16 - 8 * index))
+    });
+
+    for index in 0..4 {
+      if index <= chunk.len() {
+        let sextet = (group >> (18 - 6 * index)) & 0x3f;
+        encoded.push(char::from(BASE64_ALPHABET[sextet as usize]));
+      } else {
+        encoded.push('=');
+      }
+    }
+  }
+
+  match line_length {
+    Some(line_length) if line_length > 0 && encoded.len() > line_length => encoded
+      .as_bytes()
+      .chunks(line_length)
+      .map(|line| std::str::from_utf8(line).unwrap())
+      .collect::<Vec<_>>()
+      .join("\n"),
+    _ => encoded,
+  }
+}
+
+/// Decode a base64 value. Whitespace is ignored, so that line-wrapped values are accepted, and
+/// the trailing `=` padding is optional.
+pub fn decode_base64(value: &str) -> Result<Vec<u8>, String> {
+  let invalid = || format!("Invalid base64 value {:?}", value);
+
+  let mut sextets = value
+    .bytes()
+    .filter(|byte| !byte.is_ascii_whitespace())
+    .collect::<Vec<_>>();
+
+  let padding = sextets
+    .iter()
+    .rev()
+    .take_while(|byte| **byte == b'=')
+    .count();
+  if padding > 2 || (padding > 0 && sextets.len() % 4 != 0) {
+    return Err(invalid());
+  }
+  sextets.truncate(sextets.len() - padding);
+  if sextets.len() % 4 == 1 {
+    return Err(invalid());
+  }
+
+  let mut bytes = Vec::with_capacity(sextets.len() * 3 / 4);
+  for chunk in sextets.chunks(4) {
+    let group = chunk
+      .iter()
+      .enumerate()
+      .try_fold(0u32, |group, (index, character)| {
+        let sextet = BASE64_ALPHABET
+          .iter()
+          .position(|candidate| candidate == character)?;
+        Some(group | ((sextet as u32) << (18 - 6 * index)))
+      })
+      .ok_or_else(invalid)?;
+
+    bytes.extend(group.to_be_bytes()[1..chunk.len()].iter());
+  }
+
+  Ok(bytes)
+}
+
+/// Encode `bytes` in uppercase hexadecimal, the canonical `xs:hexBinary` form.
+pub fn encode_hex(bytes: &[u8]) -> String {
+  use std::fmt::Write;
+
+  bytes.iter().fold(
+    String::with_capacity(bytes.len() * 2),
+    |mut encoded, byte| {
+      let _ = write!(encoded, "{:02X}", byte);
+      encoded
+    },
+  )
+}
+
+/// Decode a hexadecimal value, in upper or lower case. Surrounding whitespace is ignored.
+pub fn decode_hex(value: &str) -> Result<Vec<u8>, String> {
+  let invalid = || format!("Invalid hexadecimal value {:?}", value);
+
+  let digits = value.trim().as_bytes();
+  if digits.len() % 2 != 0 {
+    return Err(invalid());
+  }
+
+  digits
+    .chunks(2)
+    .map(|pair| {
+      std::str::from_utf8(pair)
+        .ok()
+        .filter(|pair| pair.bytes().all(|byte| byte.is_ascii_hexdigit()))
+        .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        .ok_or_else(invalid)
+    })
+    .collect()
+}
+
+/// Byte buffers which can be built from decoded bytes.
+pub trait FromBytes: Sized {
+  fn from_bytes(bytes: Vec<u8>) -> Result<Self, String>;
+}
+
+impl FromBytes for Vec<u8> {
+  fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+    Ok(bytes)
+  }
+}
+
+impl FromBytes for Box<[u8]> {
+  fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+    Ok(bytes.into_boxed_slice())
+  }
+}
+
+impl<const N: usize> FromBytes for [u8; N] {
+  fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+    let length = bytes.len();
+    <[u8; N] as std::convert::TryFrom<Vec<u8>>>::try_from(bytes)
+      .map_err(|_| format!("Expected {} bytes, found {}", N, length))
+  }
+}
+
+#[cfg(feature = "bytes")]
+impl FromBytes for bytes::Bytes {
+  fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+    Ok(bytes.into())
+  }
+}
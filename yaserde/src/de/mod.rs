@@ -2,22 +2,54 @@
 //!
 
 use crate::YaDeserialize;
+use std::collections::BTreeMap;
 use std::io::Read;
 use xml::name::OwnedName;
+use xml::namespace::Namespace;
 use xml::reader::{EventReader, ParserConfig, XmlEvent};
+use xml::writer::EmitterConfig;
 
 pub fn from_str<T: YaDeserialize>(s: &str) -> Result<T, String> {
   from_reader(s.as_bytes())
 }
 
+/// Deserialize XML from a String with control on the accepted input
+pub fn from_str_with_config<T: YaDeserialize>(s: &str, config: &Config) -> Result<T, String> {
+  from_reader_with_config(s.as_bytes(), config)
+}
+
 pub fn from_reader<R: Read, T: YaDeserialize>(reader: R) -> Result<T, String> {
   <T as YaDeserialize>::deserialize(&mut Deserializer::new_from_reader(reader))
 }
 
+pub fn from_reader_with_config<R: Read, T: YaDeserialize>(
+  reader: R,
+  config: &Config,
+) -> Result<T, String> {
+  let mut deserializer = Deserializer::new_from_reader(reader);
+  deserializer.set_config(config);
+  <T as YaDeserialize>::deserialize(&mut deserializer)
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+  /// Fail on any unexpected element, attribute or text content, as if every struct had the
+  /// `deny_unknown_fields` attribute
+  pub deny_unknown_fields: bool,
+  /// URIs of the prefixes used without declaration by the deserialized types, like the
+  /// `namespaces` of `yaserde::ser::Config`; a prefix missing here takes the URI bound to it by
+  /// the document
+  pub namespaces: BTreeMap<String, String>,
+}
+
 pub struct Deserializer<R: Read> {
   depth: usize,
   reader: EventReader<R>,
   peeked: Option<XmlEvent>,
+  deny_unknown_fields: bool,
+  namespaces: BTreeMap<String, String>,
+  flattened: bool,
+  scope: Namespace,
 }
 
 impl<R: Read> Deserializer<R> {
@@ -26,6 +58,10 @@ impl<R: Read> Deserializer<R> {
       depth: 0,
       reader,
       peeked: None,
+      deny_unknown_fields: false,
+      namespaces: BTreeMap::new(),
+      flattened: false,
+      scope: Namespace::empty(),
     }
   }
 
@@ -60,6 +96,10 @@ impl<R: Read> Deserializer<R> {
             XmlEvent::StartDocument { .. }
             | XmlEvent::ProcessingInstruction { .. }
             | XmlEvent::Comment(_) => { /* skip */ }
+            XmlEvent::StartElement { ref namespace, .. } => {
+              self.scope = namespace.clone();
+              return Ok(next);
+            }
             other => return Ok(other),
           }
         }
@@ -103,6 +143,69 @@ impl<R: Read> Deserializer<R> {
     self.depth
   }
 
+  pub fn deny_unknown_fields(&self) -> bool {
+    self.deny_unknown_fields
+  }
+
+  pub fn set_deny_unknown_fields(&mut self, state: bool) {
+    self.deny_unknown_fields = state;
+  }
+
+  /// Read with this config, see `from_reader_with_config`.
+  pub fn set_config(&mut self, config: &Config) {
+    self.deny_unknown_fields = config.deny_unknown_fields;
+    self.namespaces = config.namespaces.clone();
+  }
+
+  /// The config of this deserializer, to read a nested document the same way.
+  pub fn config(&self) -> Config {
+    Config {
+      deny_unknown_fields: self.deny_unknown_fields,
+      namespaces: self.namespaces.clone(),
+    }
+  }
+
+  /// Namespace URI of `name` as matched by a derived type, which uses the `undeclared` prefixes
+  /// without declaring them.
+  ///
+  /// An undeclared prefix is bound to its URI in the `namespaces` of the config, or else in the
+  /// last read element. The namespace of `name` is then replaced by the first prefix bound to it,
+  /// after a `\0` which no URI contains, as the derived types match it.
+  #[doc(hidden)]
+  pub fn __resolve_namespace(&self, name: &OwnedName, undeclared: &[&str]) -> Option<String> {
+    let namespace = name.namespace.as_deref()?;
+
+    let prefix = undeclared.iter().find(|prefix| {
+      self
+        .namespaces
+        .get(**prefix)
+        .map(String::as_str)
+        .or_else(|| self.scope.get(**prefix))
+        == Some(namespace)
+    });
+
+    match prefix {
+      Some(prefix) => Some(format!("\0{}", prefix)),
+      None => Some(namespace.to_string()),
+    }
+  }
+
+  /// Whether the root element is the element of a struct flattening the deserialized type.
+  ///
+  /// The attributes, text content and child elements of that element are then checked by the
+  /// flattening struct, which knows what its other fields read.
+  pub fn flattened(&self) -> bool {
+    self.flattened
+  }
+
+  /// Read a flattened field from the XML left unread by its struct, with the same config.
+  pub fn read_flattened<T: YaDeserialize>(&self, content: &str) -> Result<T, String> {
+    let mut deserializer = Deserializer::new_from_reader(content.as_bytes());
+    deserializer.set_config(&self.config());
+    deserializer.flattened = true;
+    T::deserialize(&mut deserializer)
+  }
+
   pub fn read_inner_value<T, F: FnOnce(&mut Self) -> Result<T, String>>(
     &mut self,
     f: F,
@@ -116,6 +219,56 @@ impl<R: Read> Deserializer<R> {
     }
   }
 
+  /// Read the text content of the current element, consuming the element itself.
+  pub fn read_inner_text(&mut self) -> Result<String, String> {
+    self.read_inner_value(|reader| {
+      if let XmlEvent::Characters(ref text) = *reader.peek()? {
+        let text = text.clone();
+        reader.next_event()?;
+        Ok(text)
+      } else {
+        Ok(String::new())
+      }
+    })
+  }
+
+  /// Read the current element into a standalone XML document.
+  ///
+  /// The matching `EndElement` is copied but left unconsumed, like any other deserializer would.
+  pub fn read_element_to_string(&mut self) -> Result<String, String> {
+    if !matches!(self.peek()?, XmlEvent::StartElement { .. }) {
+      return Err("Start element not found".to_string());
+    }
+
+    let start_depth = self.depth;
+    let mut output = Vec::new();
+    let mut writer = EmitterConfig::new()
+      .write_document_declaration(false)
+      .create_writer(&mut output);
+
+    loop {
+      let event = self.peek()?.to_owned();
+      let last = match event {
+        XmlEvent::EndElement { .. } => self.depth == start_depth + 1,
+        XmlEvent::EndDocument => {
+          return Err("End of document, missing some content ?".to_string());
+        }
+        _ => false,
+      };
+
+      if let Some(writer_event) = event.as_writer_event() {
+        writer.write(writer_event).map_err(|e| e.to_string())?;
+      }
+
+      if last {
+        break;
+      }
+      self.next_event()?;
+    }
+
+    String::from_utf8(output).map_err(|e| e.to_string())
+  }
+
   pub fn expect_end_element(&mut self, start_name: &OwnedName) -> Result<(), String> {
     if let XmlEvent::EndElement { name, .. } = self.next_event()? {
       if name == *start_name {
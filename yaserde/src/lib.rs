@@ -89,8 +89,10 @@ extern crate yaserde_derive;
 pub use yaserde_derive::*;
 
 use std::io::{Read, Write};
+use xml::name::OwnedName;
 use xml::writer::XmlEvent;
 
+pub mod binary;
 pub mod de;
 pub mod primitives;
 pub mod ser;
@@ -98,6 +100,36 @@ pub mod ser;
 /// A **data structure** that can be deserialized from any data format supported by YaSerDe.
 pub trait YaDeserialize: Sized {
   fn deserialize<R: Read>(reader: &mut de::Deserializer<R>) -> Result<Self, String>;
+
+  /// Build the value straight from an attribute value or a text content.
+  ///
+  /// Returns `None` for types without such a representation, which are then read as an element.
+  fn deserialize_value(_value: &str) -> Option<Result<Self, String>> {
+    None
+  }
+
+  /// Whether the type reads an attribute of this name when it is flattened into a struct.
+  ///
+  /// Checked by `deny_unknown_fields` on that struct, with its `reader` resolving the prefixes of
+  /// the type. Types which don't tell accept any attribute.
+  fn accepts_flattened_attribute<R: Read>(
+    _reader: &de::Deserializer<R>,
+    _name: &OwnedName,
+  ) -> bool {
+    true
+  }
+
+  /// Whether the type reads a child element of this name when it is flattened into a struct, see
+  /// `accepts_flattened_attribute`.
+  fn accepts_flattened_element<R: Read>(_reader: &de::Deserializer<R>, _name: &OwnedName) -> bool {
+    true
+  }
+
+  /// Whether the type reads the text content when it is flattened into a struct, see
+  /// `accepts_flattened_attribute`.
+  fn accepts_flattened_text() -> bool {
+    true
+  }
 }
 
 /// A **data structure** that can be serialized into any data format supported by YaSerDe.
@@ -115,6 +147,13 @@ pub trait YaSerialize: Sized {
     ),
     String,
   >;
+
+  /// Write the value straight as an attribute value or a text content.
+  ///
+  /// Returns `None` for types without such a representation, which are then written as an element.
+  fn serialize_value(&self) -> Option<Result<String, String>> {
+    None
+  }
 }
 
 /// A **visitor** that can be implemented to retrieve information from source file.
@@ -126,6 +165,10 @@ pub trait Visitor<'de>: Sized {
     Err(format!("Unexpected bool {:?}", v))
   }
 
+  fn visit_char(self, v: &str) -> Result<Self::Value, String> {
+    Err(format!("Unexpected char {:?}", v))
+  }
+
   fn visit_i8(self, v: &str) -> Result<Self::Value, String> {
     Err(format!("Unexpected i8 {:?}", v))
   }
@@ -158,6 +201,22 @@ pub trait Visitor<'de>: Sized {
     Err(format!("Unexpected u64 {:?}", v))
   }
 
+  fn visit_i128(self, v: &str) -> Result<Self::Value, String> {
+    Err(format!("Unexpected i128 {:?}", v))
+  }
+
+  fn visit_u128(self, v: &str) -> Result<Self::Value, String> {
+    Err(format!("Unexpected u128 {:?}", v))
+  }
+
+  fn visit_isize(self, v: &str) -> Result<Self::Value, String> {
+    Err(format!("Unexpected isize {:?}", v))
+  }
+
+  fn visit_usize(self, v: &str) -> Result<Self::Value, String> {
+    Err(format!("Unexpected usize {:?}", v))
+  }
+
   fn visit_f32(self, v: &str) -> Result<Self::Value, String> {
     Err(format!("Unexpected f32 {:?}", v))
   }
@@ -198,23 +257,83 @@ macro_rules! serialize_type {
   };
 }
 
-serialize_type!(bool);
-serialize_type!(char);
+include!("simple_types.rs");
+
+/// `String` is serialized by its own implementation.
+macro_rules! serialize_simple_types {
+  (
+    FieldString => $string_ident:literal, $string_visitor:literal, $string_type:ty;
+    $($variant:ident => $ident:literal, $visitor:literal, $type:ty;)*
+  ) => {
+    $(serialize_type!($type);)*
+  };
+}
+
+with_simple_types!(serialize_simple_types);
+
+/// Implement `YaSerialize` and `YaDeserialize` for a type written as a plain value,
+/// in element, attribute and text positions.
+///
+/// With `try`, the value is written by a fallible function.
+#[allow(unused_macros)]
+macro_rules! value_type {
+  ($type:ty, $name:expr, try $to_string:expr, $from_str:expr) => {
+    impl $crate::YaSerialize for $type {
+      fn serialize<W: ::std::io::Write>(
+        &self,
+        writer: &mut $crate::ser::Serializer<W>,
+      ) -> Result<(), String> {
+        let to_string: fn(&$type) -> Result<String, String> = $to_string;
+        let value = to_string(self)?;
+        $crate::primitives::serialize_primitives(&value, $name, writer, String::clone)
+      }
+
+      fn serialize_attributes(
+        &self,
+        attributes: Vec<::xml::attribute::OwnedAttribute>,
+        namespace: ::xml::namespace::Namespace,
+      ) -> Result<
+        (
+          Vec<::xml::attribute::OwnedAttribute>,
+          ::xml::namespace::Namespace,
+        ),
+        String,
+      > {
+        Ok((attributes, namespace))
+      }
+
+      fn serialize_value(&self) -> Option<Result<String, String>> {
+        let to_string: fn(&$type) -> Result<String, String> = $to_string;
+        Some(to_string(self))
+      }
+    }
 
-serialize_type!(usize);
-serialize_type!(u8);
-serialize_type!(u16);
-serialize_type!(u32);
-serialize_type!(u64);
+    impl $crate::YaDeserialize for $type {
+      fn deserialize<R: ::std::io::Read>(
+        reader: &mut $crate::de::Deserializer<R>,
+      ) -> Result<Self, String> {
+        let from_str: fn(&str) -> Result<$type, String> = $from_str;
+        $crate::primitives::deserialize_primitives(reader, from_str)
+      }
 
-serialize_type!(isize);
-serialize_type!(i8);
-serialize_type!(i16);
-serialize_type!(i32);
-serialize_type!(i64);
+      fn deserialize_value(value: &str) -> Option<Result<Self, String>> {
+        let from_str: fn(&str) -> Result<$type, String> = $from_str;
+        Some(from_str(value))
+      }
+    }
+  };
+  ($type:ty, $name:expr, $to_string:expr, $from_str:expr) => {
+    value_type!(
+      $type,
+      $name,
+      try |value: &$type| Ok::<String, String>($to_string(value)),
+      $from_str
+    );
+  };
+}
 
-serialize_type!(f32);
-serialize_type!(f64);
+mod types;
+pub mod xsd;
 
 /// Re-export for use in yaserde_derive
 #[doc(hidden)]
@@ -224,6 +343,39 @@ pub use xml as __xml;
 #[doc(hidden)]
 pub use log as __log;
 
+/// Declare namespaces shared by many types, which refer to the module with
+/// `#[yaserde(namespaces = path::to::module)]` instead of listing them.
+///
+/// ```
+/// yaserde::namespaces! {
+///   pub mod soap_namespaces {
+///     xs = "http://www.w3.org/2001/XMLSchema",
+///     soap = "http://schemas.xmlsoap.org/soap/envelope/",
+///   }
+/// }
+///
+/// assert_eq!(soap_namespaces::xs, "http://www.w3.org/2001/XMLSchema");
+/// assert_eq!(soap_namespaces::NAMESPACES.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! namespaces {
+  (
+    $(#[$meta:meta])*
+    $vis:vis mod $name:ident {
+      $($prefix:ident = $namespace:expr),* $(,)?
+    }
+  ) => {
+    $(#[$meta])*
+    #[allow(non_upper_case_globals)]
+    $vis mod $name {
+      $(pub const $prefix: &str = $namespace;)*
+
+      /// Prefixes and URIs of all the namespaces
+      pub const NAMESPACES: &[(&str, &str)] = &[$((stringify!($prefix), $prefix)),*];
+    }
+  };
+}
+
 /// Used in code generated by yaserde_derive for logging
 #[macro_export]
 #[doc(hidden)]
@@ -241,7 +393,7 @@ macro_rules! __derive_trace {
 #[test]
 fn default_visitor() {
   struct Test;
-  impl<'de> Visitor<'de> for Test {
+  impl Visitor<'_> for Test {
     type Value = u8;
   }
 
@@ -253,6 +405,7 @@ fn default_visitor() {
   }
 
   test_type!(visit_bool, "Unexpected bool \"\"");
+  test_type!(visit_char, "Unexpected char \"\"");
   test_type!(visit_i8, "Unexpected i8 \"\"");
   test_type!(visit_u8, "Unexpected u8 \"\"");
   test_type!(visit_i16, "Unexpected i16 \"\"");
@@ -261,6 +414,10 @@ fn default_visitor() {
   test_type!(visit_u32, "Unexpected u32 \"\"");
   test_type!(visit_i64, "Unexpected i64 \"\"");
   test_type!(visit_u64, "Unexpected u64 \"\"");
+  test_type!(visit_i128, "Unexpected i128 \"\"");
+  test_type!(visit_u128, "Unexpected u128 \"\"");
+  test_type!(visit_isize, "Unexpected isize \"\"");
+  test_type!(visit_usize, "Unexpected usize \"\"");
   test_type!(visit_str, "Unexpected str \"\"");
 }
 
@@ -2,10 +2,17 @@
 //!
 
 use crate::YaSerialize;
+use namespaces::NamespaceScopes;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::io::{Cursor, Write};
 use std::str;
+use xml::common::XmlVersion;
+use xml::reader::XmlEvent as ReaderEvent;
 use xml::writer::XmlEvent;
-use xml::{EmitterConfig, EventWriter};
+use xml::{EmitterConfig, EventReader, EventWriter};
+
+mod namespaces;
 
 /// Serialize XML into a plain String with no formatting (EmitterConfig).
 pub fn to_string<T: YaSerialize>(model: &T) -> Result<String, String> {
@@ -29,6 +36,16 @@ pub fn serialize_with_writer<W: Write, T: YaSerialize>(
   config: &Config,
 ) -> Result<W, String> {
   let mut serializer = Serializer::new_from_writer(writer, config);
+  // The declaration is written here, as the encoding name written by xml-rs depends on its version
+  if config.write_document_declaration {
+    serializer
+      .write(XmlEvent::StartDocument {
+        version: XmlVersion::Version10,
+        encoding: Some("utf-8"),
+        standalone: None,
+      })
+      .map_err(|error| error.to_string())?;
+  }
   match YaSerialize::serialize(model, &mut serializer) {
     Ok(()) => Ok(serializer.into_inner()),
     Err(msg) => Err(msg),
@@ -42,6 +59,13 @@ pub fn to_string_content<T: YaSerialize>(model: &T) -> Result<String, String> {
   Ok(data.into())
 }
 
+/// Serialize a model as an attribute value or a text content.
+pub fn to_string_value<T: YaSerialize>(model: &T) -> Result<String, String> {
+  model
+    .serialize_value()
+    .unwrap_or_else(|| to_string_content(model))
+}
+
 pub fn serialize_with_writer_content<W: Write, T: YaSerialize>(
   model: &T,
   writer: W,
@@ -58,6 +82,7 @@ pub struct Serializer<W: Write> {
   writer: EventWriter<W>,
   skip_start_end: bool,
   start_event_name: Option<String>,
+  namespaces: NamespaceScopes,
 }
 
 impl<W: Write> Serializer<W> {
@@ -66,6 +91,7 @@ impl<W: Write> Serializer<W> {
       writer,
       skip_start_end: false,
       start_event_name: None,
+      namespaces: NamespaceScopes::default(),
     }
   }
 
@@ -79,7 +105,10 @@ impl<W: Write> Serializer<W> {
       emitter_config = emitter_config.indent_string(indent_string_value.clone());
     }
 
-    Self::new(EventWriter::new_with_config(writer, emitter_config))
+    let mut serializer = Self::new(EventWriter::new_with_config(writer, emitter_config));
+    serializer.namespaces =
+      NamespaceScopes::new(config.namespaces.clone(), config.hoist_namespaces);
+    serializer
   }
 
   pub fn new_for_inner(writer: W) -> Self {
@@ -108,18 +137,75 @@ impl<W: Write> Serializer<W> {
     self.start_event_name = name;
   }
 
+  /// Write an event.
+  ///
+  /// The namespaces declared by start elements are tracked: a declaration already in scope is not
+  /// written again, and names given with a namespace URI get the prefix in scope for it, the
+  /// preferred one of the `Config`, or a new one.
   pub fn write<'a, E>(&mut self, event: E) -> xml::writer::Result<()>
   where
     E: Into<XmlEvent<'a>>,
   {
-    self.writer.write(event)
+    match event.into() {
+      XmlEvent::StartElement {
+        name,
+        attributes,
+        namespace,
+      } => {
+        let mut name = name.to_owned();
+        let mut attributes = attributes
+          .iter()
+          .map(|attribute| attribute.to_owned())
+          .collect();
+        let namespace = self
+          .namespaces
+          .start_element(&mut name, &mut attributes, &namespace);
+
+        self.writer.write(XmlEvent::StartElement {
+          name: name.borrow(),
+          attributes: attributes
+            .iter()
+            .map(|attribute| attribute.borrow())
+            .collect(),
+          namespace: Cow::Owned(namespace),
+        })
+      }
+      event @ XmlEvent::EndElement { .. } => {
+        self.namespaces.end_element();
+        self.writer.write(event)
+      }
+      event => self.writer.write(event),
+    }
+  }
+
+  /// Write an element read by `Deserializer::read_element_to_string`.
+  pub fn write_element_string(&mut self, content: &str) -> Result<(), String> {
+    let mut reader = EventReader::new(content.as_bytes());
+
+    loop {
+      match reader.next().map_err(|error| error.to_string())? {
+        ReaderEvent::StartDocument { .. } => {}
+        ReaderEvent::EndDocument => return Ok(()),
+        event => {
+          if let Some(event) = event.as_writer_event() {
+            self.write(event).map_err(|error| error.to_string())?;
+          }
+        }
+      }
+    }
   }
 }
 
 pub struct Config {
   pub perform_indent: bool,
+  /// Start the document with `<?xml version="1.0" encoding="utf-8"?>`
   pub write_document_declaration: bool,
   pub indent_string: Option<String>,
+  /// Preferred prefixes of namespace URIs, declared where a prefix is used without being declared
+  /// by the serialized types, and used for the names written with a namespace URI
+  pub namespaces: BTreeMap<String, String>,
+  /// Declare all the `namespaces` on the root element, instead of where they are first used
+  pub hoist_namespaces: bool,
 }
 
 impl Default for Config {
@@ -128,6 +214,8 @@ impl Default for Config {
       perform_indent: false,
       write_document_declaration: true,
       indent_string: None,
+      namespaces: BTreeMap::new(),
+      hoist_namespaces: false,
     }
   }
 }
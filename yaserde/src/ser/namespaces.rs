@@ -0,0 +1,175 @@
+//! Namespace declarations in scope while writing a document.
+
+use std::collections::BTreeMap;
+use xml::attribute::OwnedAttribute;
+use xml::name::OwnedName;
+use xml::namespace::{Namespace, NamespaceStack, NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX};
+
+/// Tracks the namespaces declared by the written elements, to declare each of them only once and
+/// to choose the prefix of names given with a namespace URI.
+#[derive(Default)]
+pub(crate) struct NamespaceScopes {
+  stack: Option<NamespaceStack>,
+  preferred: BTreeMap<String, String>,
+  hoist: bool,
+}
+
+impl NamespaceScopes {
+  pub(crate) fn new(preferred: BTreeMap<String, String>, hoist: bool) -> Self {
+    NamespaceScopes {
+      stack: None,
+      preferred,
+      hoist,
+    }
+  }
+
+  /// Resolve the names of a start element and return the declarations it has to write.
+  ///
+  /// Declarations already in scope are dropped, including the ones written as `xmlns`
+  /// attributes.
+  pub(crate) fn start_element(
+    &mut self,
+    name: &mut OwnedName,
+    attributes: &mut Vec<OwnedAttribute>,
+    namespace: &Namespace,
+  ) -> Namespace {
+    let is_root = self.stack.is_none();
+    let stack = self.stack.get_or_insert_with(NamespaceStack::default);
+
+    let mut declarations = namespace.clone();
+    let mut attribute_declarations = Namespace::empty();
+    attributes.retain(|attribute| match attribute_declaration(&attribute.name) {
+      Some(prefix) if stack.get(prefix) == Some(attribute.value.as_str()) => false,
+      Some(prefix) => {
+        attribute_declarations.force_put(prefix, attribute.value.clone());
+        declarations.force_put(prefix, attribute.value.clone());
+        true
+      }
+      None => true,
+    });
+
+    if is_root && self.hoist {
+      for (prefix, uri) in &self.preferred {
+        declarations.put(prefix.clone(), uri.clone());
+      }
+    }
+
+    let mut scope = Scope {
+      stack,
+      declarations: &mut declarations,
+      preferred: &self.preferred,
+    };
+    scope.resolve(name, false);
+    for attribute in attributes.iter_mut() {
+      scope.resolve(&mut attribute.name, true);
+    }
+
+    let mut written = Namespace::empty();
+    for (prefix, uri) in &declarations {
+      if stack.get(prefix) != Some(uri) && attribute_declarations.get(prefix) != Some(uri) {
+        written.put(prefix, uri);
+      }
+    }
+
+    stack.push_empty();
+    for (prefix, uri) in &declarations {
+      stack.put(prefix, uri);
+    }
+
+    written
+  }
+
+  pub(crate) fn end_element(&mut self) {
+    if let Some(stack) = &mut self.stack {
+      stack.try_pop();
+    }
+  }
+}
+
+/// Prefix declared by an `xmlns` or `xmlns:prefix` attribute
+fn attribute_declaration(name: &OwnedName) -> Option<&str> {
+  match (name.prefix.as_deref(), name.local_name.as_str()) {
+    (Some(NS_XMLNS_PREFIX), prefix) => Some(prefix),
+    (None, NS_XMLNS_PREFIX) => Some(NS_NO_PREFIX),
+    _ => None,
+  }
+}
+
+/// Namespaces in scope for the names of the element being written
+struct Scope<'a> {
+  stack: &'a NamespaceStack,
+  declarations: &'a mut Namespace,
+  preferred: &'a BTreeMap<String, String>,
+}
+
+impl Scope<'_> {
+  fn get(&self, prefix: &str) -> Option<&str> {
+    self
+      .declarations
+      .get(prefix)
+      .or_else(|| self.stack.get(prefix))
+  }
+
+  /// Give a prefix to a name with a namespace URI, and declare the prefix of a name when needed.
+  ///
+  /// Attributes are never in the default namespace, so they always get a prefix.
+  fn resolve(&mut self, name: &mut OwnedName, is_attribute: bool) {
+    let uri = match &name.namespace {
+      Some(uri) => uri.clone(),
+      None => {
+        if let Some(prefix) = &name.prefix {
+          if self.get(prefix).is_none() {
+            if let Some(uri) = self.preferred.get(prefix) {
+              self.declarations.put(prefix.clone(), uri.clone());
+            }
+          }
+        }
+        return;
+      }
+    };
+
+    let current = name.prefix.as_deref().unwrap_or(NS_NO_PREFIX);
+    if self.get(current) == Some(uri.as_str()) && !(is_attribute && current == NS_NO_PREFIX) {
+      return;
+    }
+
+    let prefix = self
+      .in_scope_prefix(&uri, is_attribute)
+      .or_else(|| self.preferred_prefix(&uri))
+      .unwrap_or_else(|| self.invent_prefix());
+
+    if self.get(&prefix).is_none() {
+      self.declarations.put(prefix.clone(), uri);
+    }
+    name.prefix = if prefix == NS_NO_PREFIX {
+      None
+    } else {
+      Some(prefix)
+    };
+  }
+
+  fn in_scope_prefix(&self, uri: &str, is_attribute: bool) -> Option<String> {
+    self
+      .declarations
+      .iter()
+      .chain(self.stack.iter())
+      .filter(|(prefix, _)| !(is_attribute && *prefix == NS_NO_PREFIX))
+      .find(|(prefix, candidate)| *candidate == uri && self.get(prefix) == Some(uri))
+      .map(|(prefix, _)| prefix.to_string())
+  }
+
+  fn preferred_prefix(&self, uri: &str) -> Option<String> {
+    self
+      .preferred
+      .iter()
+      .find(|(prefix, candidate)| *candidate == uri && self.get(prefix).is_none())
+      .map(|(prefix, _)| prefix.clone())
+  }
+
+  fn invent_prefix(&self) -> String {
+    (1..)
+      .map(|index| format!("ns{}", index))
+      .find(|prefix| prefix != NS_XML_PREFIX && self.get(prefix).is_none())
+      .unwrap()
+  }
+}
@@ -0,0 +1,41 @@
+// The primitive types read and written as simple values, shared by `yaserde` and
+// `yaserde_derive` with `include!`: the runtime implements `YaSerialize` for them, and the derive
+// reads them with the `Visitor` method of their entry.
+
+/// Invoke `$callback!` with one `Field variant => "type identifier", "visitor", type;` entry per
+/// primitive type, starting with `String`.
+macro_rules! with_simple_types {
+  ($callback:ident) => {
+    $callback! {
+      FieldString => "String", "str", ::std::string::String;
+      FieldBool => "bool", "bool", bool;
+      FieldChar => "char", "char", char;
+      FieldI8 => "i8", "i8", i8;
+      FieldU8 => "u8", "u8", u8;
+      FieldI16 => "i16", "i16", i16;
+      FieldU16 => "u16", "u16", u16;
+      FieldI32 => "i32", "i32", i32;
+      FieldU32 => "u32", "u32", u32;
+      FieldI64 => "i64", "i64", i64;
+      FieldU64 => "u64", "u64", u64;
+      FieldI128 => "i128", "i128", i128;
+      FieldU128 => "u128", "u128", u128;
+      FieldIsize => "isize", "isize", isize;
+      FieldUsize => "usize", "usize", usize;
+      FieldF32 => "f32", "f32", f32;
+      FieldF64 => "f64", "f64", f64;
+      FieldNonZeroI8 => "NonZeroI8", "i8", ::std::num::NonZeroI8;
+      FieldNonZeroU8 => "NonZeroU8", "u8", ::std::num::NonZeroU8;
+      FieldNonZeroI16 => "NonZeroI16", "i16", ::std::num::NonZeroI16;
+      FieldNonZeroU16 => "NonZeroU16", "u16", ::std::num::NonZeroU16;
+      FieldNonZeroI32 => "NonZeroI32", "i32", ::std::num::NonZeroI32;
+      FieldNonZeroU32 => "NonZeroU32", "u32", ::std::num::NonZeroU32;
+      FieldNonZeroI64 => "NonZeroI64", "i64", ::std::num::NonZeroI64;
+      FieldNonZeroU64 => "NonZeroU64", "u64", ::std::num::NonZeroU64;
+      FieldNonZeroI128 => "NonZeroI128", "i128", ::std::num::NonZeroI128;
+      FieldNonZeroU128 => "NonZeroU128", "u128", ::std::num::NonZeroU128;
+      FieldNonZeroIsize => "NonZeroIsize", "isize", ::std::num::NonZeroIsize;
+      FieldNonZeroUsize => "NonZeroUsize", "usize", ::std::num::NonZeroUsize;
+    }
+  };
+}
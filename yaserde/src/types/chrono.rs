@@ -0,0 +1,75 @@
+//! `chrono` types, read and written with the XSD `dateTime`, `date` and `time` lexical forms.
+//!
+//! The naive types also read values with a timezone: a `dateTime` or a `time` is converted to UTC,
+//! and a `date` keeps its day, the timezone being dropped.
+
+use super::split_timezone;
+use chrono::{
+  DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc,
+};
+
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+
+/// A `dateTime` without timezone is read as UTC.
+fn parse_date_time(value: &str) -> Result<DateTime<FixedOffset>, String> {
+  DateTime::parse_from_rfc3339(value)
+    .or_else(|_| {
+      NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+        .map(|date_time| DateTime::<Utc>::from_naive_utc_and_offset(date_time, Utc).into())
+    })
+    .map_err(|e| format!("Invalid dateTime {:?}: {}", value, e))
+}
+
+value_type!(
+  DateTime<Utc>,
+  "dateTime",
+  |value: &DateTime<Utc>| value.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+  |value: &str| parse_date_time(value).map(|date_time| date_time.with_timezone(&Utc))
+);
+
+value_type!(
+  DateTime<FixedOffset>,
+  "dateTime",
+  |value: &DateTime<FixedOffset>| value.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+  parse_date_time
+);
+
+value_type!(
+  NaiveDateTime,
+  "dateTime",
+  |value: &NaiveDateTime| value.format(DATE_TIME_FORMAT).to_string(),
+  |value: &str| {
+    let (local, offset) = split_timezone(value)?;
+    let date_time = NaiveDateTime::parse_from_str(local, DATE_TIME_FORMAT)
+      .map_err(|e| format!("Invalid dateTime {:?}: {}", value, e))?;
+    date_time
+      .checked_sub_signed(Duration::seconds(offset.unwrap_or_default().into()))
+      .ok_or_else(|| format!("Invalid dateTime {:?}: out of range", value))
+  }
+);
+
+value_type!(
+  NaiveDate,
+  "date",
+  |value: &NaiveDate| value.format(DATE_FORMAT).to_string(),
+  |value: &str| NaiveDate::parse_from_str(split_timezone(value)?.0, DATE_FORMAT)
+    .map_err(|e| format!("Invalid date {:?}: {}", value, e))
+);
+
+value_type!(
+  NaiveTime,
+  "time",
+  |value: &NaiveTime| value.format(TIME_FORMAT).to_string(),
+  |value: &str| {
+    let (local, offset) = split_timezone(value)?;
+    let time = NaiveTime::parse_from_str(local, TIME_FORMAT)
+      .map_err(|e| format!("Invalid time {:?}: {}", value, e))?;
+    Ok(
+      time
+        .overflowing_sub_signed(Duration::seconds(offset.unwrap_or_default().into()))
+        .0,
+    )
+  }
+);
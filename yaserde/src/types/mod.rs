@@ -0,0 +1,63 @@
+//! Implementations for standard library types and types of optional dependencies.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "chrono")]
+mod chrono;
+mod net;
+mod path;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+/// Read a value with its `FromStr` implementation.
+fn parse<T>(value: &str) -> Result<T, String>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  value.parse().map_err(|e: T::Err| e.to_string())
+}
+
+/// Split the timezone, `Z` or `±hh:mm`, from the end of an XSD date or time value, giving its
+/// offset from UTC in seconds.
+pub(crate) fn split_timezone(value: &str) -> Result<(&str, Option<i32>), String> {
+  if let Some(local) = value.strip_suffix('Z') {
+    return Ok((local, Some(0)));
+  }
+
+  let index = match value.len().checked_sub(6) {
+    Some(index) if value.is_char_boundary(index) => index,
+    _ => return Ok((value, None)),
+  };
+  let (local, timezone) = value.split_at(index);
+  let timezone = timezone.as_bytes();
+  let sign = match timezone[0] {
+    b'+' => 1,
+    b'-' => -1,
+    _ => return Ok((value, None)),
+  };
+  if timezone[3] != b':' {
+    return Ok((value, None));
+  }
+
+  let number = |digits: &[u8]| {
+    digits.iter().try_fold(0, |number, digit| {
+      digit
+        .is_ascii_digit()
+        .then(|| number * 10 + i32::from(digit - b'0'))
+    })
+  };
+  match (number(&timezone[1..3]), number(&timezone[4..6])) {
+    (Some(hours), Some(minutes)) if minutes < 60 && (hours < 14 || (hours, minutes) == (14, 0)) => {
+      Ok((local, Some(sign * (hours * 3600 + minutes * 60))))
+    }
+    _ => Err(format!("Invalid timezone in {:?}", value)),
+  }
+}
@@ -0,0 +1,11 @@
+//! IP addresses and socket addresses, in their usual text form.
+
+use super::parse;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+value_type!(IpAddr, "ipAddr", IpAddr::to_string, parse);
+value_type!(Ipv4Addr, "ipv4Addr", Ipv4Addr::to_string, parse);
+value_type!(Ipv6Addr, "ipv6Addr", Ipv6Addr::to_string, parse);
+value_type!(SocketAddr, "socketAddr", SocketAddr::to_string, parse);
+value_type!(SocketAddrV4, "socketAddrV4", SocketAddrV4::to_string, parse);
+value_type!(SocketAddrV6, "socketAddrV6", SocketAddrV6::to_string, parse);
@@ -0,0 +1,14 @@
+//! File system paths.
+
+use std::path::PathBuf;
+
+// Paths which are not valid Unicode can not be written in a document
+value_type!(
+  PathBuf,
+  "path",
+  try |value: &PathBuf| value
+    .to_str()
+    .map(str::to_string)
+    .ok_or_else(|| format!("Path {:?} is not valid Unicode", value)),
+  |value: &str| Ok(PathBuf::from(value))
+);
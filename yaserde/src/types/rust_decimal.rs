@@ -0,0 +1,6 @@
+//! `rust_decimal` numbers, as XSD `decimal`.
+
+use super::parse;
+use rust_decimal::Decimal;
+
+value_type!(Decimal, "decimal", Decimal::to_string, parse);
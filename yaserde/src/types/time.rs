@@ -0,0 +1,111 @@
+//! `time` types, read and written with the XSD `dateTime`, `date` and `time` lexical forms.
+//!
+//! `PrimitiveDateTime`, `Date` and `Time` also read values with a timezone: a `dateTime` or a
+//! `time` is converted to UTC, and a `date` keeps its day, the timezone being dropped.
+
+use super::split_timezone;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+fn format_date(date: Date) -> String {
+  format!(
+    "{:04}-{:02}-{:02}",
+    date.year(),
+    u8::from(date.month()),
+    date.day()
+  )
+}
+
+/// Fractional seconds are only written when present, without trailing zeros.
+fn format_time(time: Time) -> String {
+  let mut value = format!(
+    "{:02}:{:02}:{:02}",
+    time.hour(),
+    time.minute(),
+    time.second()
+  );
+
+  if time.nanosecond() != 0 {
+    let fraction = format!("{:09}", time.nanosecond());
+    value.push('.');
+    value.push_str(fraction.trim_end_matches('0'));
+  }
+
+  value
+}
+
+fn format_offset(offset: UtcOffset) -> String {
+  if offset.is_utc() {
+    return "Z".to_string();
+  }
+
+  let (hours, minutes, _seconds) = offset.as_hms();
+  let sign = if offset.is_negative() { '-' } else { '+' };
+  format!("{}{:02}:{:02}", sign, hours.abs(), minutes.abs())
+}
+
+fn parse_primitive_date_time(value: &str) -> Result<PrimitiveDateTime, String> {
+  PrimitiveDateTime::parse(
+    value,
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"),
+  )
+  .map_err(|e| format!("Invalid dateTime {:?}: {}", value, e))
+}
+
+value_type!(
+  OffsetDateTime,
+  "dateTime",
+  |value: &OffsetDateTime| format!(
+    "{}T{}{}",
+    format_date(value.date()),
+    format_time(value.time()),
+    format_offset(value.offset())
+  ),
+  // A `dateTime` without timezone is read as UTC
+  |value: &str| OffsetDateTime::parse(value, &Rfc3339)
+    .or_else(|_| parse_primitive_date_time(value).map(PrimitiveDateTime::assume_utc))
+);
+
+value_type!(
+  PrimitiveDateTime,
+  "dateTime",
+  |value: &PrimitiveDateTime| format!(
+    "{}T{}",
+    format_date(value.date()),
+    format_time(value.time())
+  ),
+  |value: &str| {
+    let (local, offset) = split_timezone(value)?;
+    parse_primitive_date_time(local)
+      .map_err(|_| format!("Invalid dateTime {:?}", value))?
+      .checked_sub(Duration::seconds(offset.unwrap_or_default().into()))
+      .ok_or_else(|| format!("Invalid dateTime {:?}: out of range", value))
+  }
+);
+
+value_type!(
+  Date,
+  "date",
+  |value: &Date| format_date(*value),
+  |value: &str| Date::parse(
+    split_timezone(value)?.0,
+    format_description!("[year]-[month]-[day]"),
+  )
+  .map_err(|e| format!("Invalid date {:?}: {}", value, e))
+);
+
+value_type!(
+  Time,
+  "time",
+  |value: &Time| format_time(*value),
+  |value: &str| {
+    let (local, offset) = split_timezone(value)?;
+    let time = Time::parse(
+      local,
+      format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]"),
+    )
+    .map_err(|e| format!("Invalid time {:?}: {}", value, e))?;
+    Ok(time - Duration::seconds(offset.unwrap_or_default().into()))
+  }
+);
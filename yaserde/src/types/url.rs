@@ -0,0 +1,6 @@
+//! `url` absolute URLs.
+
+use super::parse;
+use url::Url;
+
+value_type!(Url, "url", Url::to_string, parse);
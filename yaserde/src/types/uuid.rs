@@ -0,0 +1,6 @@
+//! `uuid` identifiers, written in their hyphenated form.
+
+use super::parse;
+use uuid::Uuid;
+
+value_type!(Uuid, "uuid", Uuid::to_string, parse);
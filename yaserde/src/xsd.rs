@@ -0,0 +1,715 @@
+//! XSD date and duration types that are not full timestamps: `gYear`, `gYearMonth`, `gMonthDay`
+//! and `duration`.
+//!
+//! Each type validates its lexical form on parsing, is written in its canonical form, and can be
+//! used as an element, an attribute value or a text content.
+//!
+//! Partial dates are ordered on the instant they start at, with `gMonthDay` values placed in a
+//! leap year. A value without timezone is only comparable with a value with timezone when they
+//! are more than 14 hours apart.
+//!
+//! ```
+//! use yaserde::xsd::{Duration, GYearMonth};
+//!
+//! let month: GYearMonth = "2024-02Z".parse().unwrap();
+//! assert_eq!(month.year(), 2024);
+//! assert_eq!(month.month(), 2);
+//!
+//! let duration: Duration = "PT90M".parse().unwrap();
+//! assert_eq!(duration.to_string(), "PT1H30M");
+//! ```
+
+use crate::types;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+const MAX_TIMEZONE_MINUTES: i16 = 14 * 60;
+
+fn check_timezone(offset_minutes: i16) -> Result<i16, String> {
+  if offset_minutes.abs() > MAX_TIMEZONE_MINUTES {
+    return Err(format!(
+      "Timezone offset of {} minutes is out of the -14:00..+14:00 range",
+      offset_minutes
+    ));
+  }
+  Ok(offset_minutes)
+}
+
+fn check_month(month: u8) -> Result<u8, String> {
+  if !(1..=12).contains(&month) {
+    return Err(format!("Month {} is out of the 1..12 range", month));
+  }
+  Ok(month)
+}
+
+/// Parse exactly `width` ASCII digits.
+fn parse_digits(value: &str, width: usize) -> Option<u8> {
+  if value.len() != width || !value.bytes().all(|b| b.is_ascii_digit()) {
+    return None;
+  }
+  value.parse().ok()
+}
+
+/// A year has at least four digits, without leading zeros beyond those.
+fn parse_year(value: &str) -> Option<i32> {
+  let digits = value.strip_prefix('-').unwrap_or(value);
+  if digits.len() < 4
+    || !digits.bytes().all(|b| b.is_ascii_digit())
+    || (digits.len() > 4 && digits.starts_with('0'))
+  {
+    return None;
+  }
+  value.parse().ok()
+}
+
+/// Split the optional `Z` or `±hh:mm` timezone suffix, returned in minutes.
+fn split_timezone(value: &str) -> Result<(&str, Option<i16>), String> {
+  let (value, offset) = types::split_timezone(value)?;
+  // The offset is a whole number of minutes within ±14:00
+  Ok((value, offset.map(|offset| (offset / 60) as i16)))
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+  let year = i64::from(year) - i64::from(month <= 2);
+  let era = year.div_euclid(400);
+  let year_of_era = year.rem_euclid(400);
+  let month = i64::from(month);
+  let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  era * 146_097 + day_of_era - 719_468
+}
+
+/// Compare the starting instants of two values, given as a day and an optional timezone.
+///
+/// As in XML Schema, a value without timezone may be anywhere from -14:00 to +14:00, so it is only
+/// ordered against a value with timezone when both extremes agree. Values starting at the same
+/// instant with different fields, e.g. `--03-01+14:00` and `--02-29-10:00`, are not comparable,
+/// to stay consistent with equality.
+fn compare_starts(
+  (day, timezone): (i64, Option<i16>),
+  (other_day, other_timezone): (i64, Option<i16>),
+) -> Option<Ordering> {
+  let start = |day: i64, timezone: i16| day * 24 * 60 - i64::from(timezone);
+
+  let ordering = match (timezone, other_timezone) {
+    (Some(timezone), Some(other_timezone)) => {
+      start(day, timezone).cmp(&start(other_day, other_timezone))
+    }
+    (None, None) => day.cmp(&other_day),
+    (None, Some(other_timezone)) => {
+      let other_start = start(other_day, other_timezone);
+      let earliest = start(day, MAX_TIMEZONE_MINUTES).cmp(&other_start);
+      let latest = start(day, -MAX_TIMEZONE_MINUTES).cmp(&other_start);
+      if earliest != latest {
+        return None;
+      }
+      earliest
+    }
+    (Some(_), None) => {
+      return compare_starts((other_day, other_timezone), (day, timezone)).map(Ordering::reverse)
+    }
+  };
+
+  match ordering {
+    Ordering::Equal if (day, timezone) != (other_day, other_timezone) => None,
+    ordering => Some(ordering),
+  }
+}
+
+fn write_year(f: &mut fmt::Formatter<'_>, year: i32) -> fmt::Result {
+  if year < 0 {
+    write!(f, "-{:04}", year.unsigned_abs())
+  } else {
+    write!(f, "{:04}", year)
+  }
+}
+
+fn write_timezone(f: &mut fmt::Formatter<'_>, timezone: Option<i16>) -> fmt::Result {
+  match timezone {
+    None => Ok(()),
+    Some(0) => write!(f, "Z"),
+    Some(offset) => {
+      let sign = if offset < 0 { '-' } else { '+' };
+      let offset = offset.unsigned_abs();
+      write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+    }
+  }
+}
+
+/// An `xs:gYear` value, e.g. `2024` or `-0044+01:00`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GYear {
+  year: i32,
+  timezone: Option<i16>,
+}
+
+impl GYear {
+  pub fn new(year: i32) -> Self {
+    GYear {
+      year,
+      timezone: None,
+    }
+  }
+
+  /// Set the timezone offset, in minutes.
+  pub fn with_timezone(self, offset_minutes: i16) -> Result<Self, String> {
+    Ok(GYear {
+      timezone: Some(check_timezone(offset_minutes)?),
+      ..self
+    })
+  }
+
+  pub fn year(&self) -> i32 {
+    self.year
+  }
+
+  /// The timezone offset in minutes, if any.
+  pub fn timezone(&self) -> Option<i16> {
+    self.timezone
+  }
+}
+
+impl PartialOrd for GYear {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    compare_starts(
+      (days_from_civil(self.year, 1, 1), self.timezone),
+      (days_from_civil(other.year, 1, 1), other.timezone),
+    )
+  }
+}
+
+impl fmt::Display for GYear {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_year(f, self.year)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+impl FromStr for GYear {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid gYear {:?}", value);
+    if !value.is_ascii() {
+      return Err(invalid());
+    }
+
+    let (value_part, timezone) = split_timezone(value)?;
+    let year = parse_year(value_part).ok_or_else(invalid)?;
+
+    Ok(GYear { year, timezone })
+  }
+}
+
+/// An `xs:gYearMonth` value, e.g. `2024-02` or `2024-02Z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GYearMonth {
+  year: i32,
+  month: u8,
+  timezone: Option<i16>,
+}
+
+impl GYearMonth {
+  pub fn new(year: i32, month: u8) -> Result<Self, String> {
+    Ok(GYearMonth {
+      year,
+      month: check_month(month)?,
+      timezone: None,
+    })
+  }
+
+  /// Set the timezone offset, in minutes.
+  pub fn with_timezone(self, offset_minutes: i16) -> Result<Self, String> {
+    Ok(GYearMonth {
+      timezone: Some(check_timezone(offset_minutes)?),
+      ..self
+    })
+  }
+
+  pub fn year(&self) -> i32 {
+    self.year
+  }
+
+  pub fn month(&self) -> u8 {
+    self.month
+  }
+
+  /// The timezone offset in minutes, if any.
+  pub fn timezone(&self) -> Option<i16> {
+    self.timezone
+  }
+}
+
+impl PartialOrd for GYearMonth {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    compare_starts(
+      (days_from_civil(self.year, self.month, 1), self.timezone),
+      (days_from_civil(other.year, other.month, 1), other.timezone),
+    )
+  }
+}
+
+impl fmt::Display for GYearMonth {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_year(f, self.year)?;
+    write!(f, "-{:02}", self.month)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+impl FromStr for GYearMonth {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid gYearMonth {:?}", value);
+    if !value.is_ascii() {
+      return Err(invalid());
+    }
+
+    let (value_part, timezone) = split_timezone(value)?;
+    let (year, month) = value_part.rsplit_once('-').ok_or_else(invalid)?;
+    let year = parse_year(year).ok_or_else(invalid)?;
+    let month = parse_digits(month, 2).ok_or_else(invalid)?;
+
+    Ok(GYearMonth {
+      year,
+      month: check_month(month).map_err(|_| invalid())?,
+      timezone,
+    })
+  }
+}
+
+/// An `xs:gMonthDay` value, e.g. `--02-29` or `--12-25+01:00`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GMonthDay {
+  month: u8,
+  day: u8,
+  timezone: Option<i16>,
+}
+
+impl GMonthDay {
+  /// February 29th is accepted, as it exists in leap years.
+  pub fn new(month: u8, day: u8) -> Result<Self, String> {
+    let month = check_month(month)?;
+    let days_in_month = match month {
+      2 => 29,
+      4 | 6 | 9 | 11 => 30,
+      _ => 31,
+    };
+    if day == 0 || day > days_in_month {
+      return Err(format!(
+        "Day {} is out of the 1..{} range of month {}",
+        day, days_in_month, month
+      ));
+    }
+
+    Ok(GMonthDay {
+      month,
+      day,
+      timezone: None,
+    })
+  }
+
+  /// Set the timezone offset, in minutes.
+  pub fn with_timezone(self, offset_minutes: i16) -> Result<Self, String> {
+    Ok(GMonthDay {
+      timezone: Some(check_timezone(offset_minutes)?),
+      ..self
+    })
+  }
+
+  pub fn month(&self) -> u8 {
+    self.month
+  }
+
+  pub fn day(&self) -> u8 {
+    self.day
+  }
+
+  /// The timezone offset in minutes, if any.
+  pub fn timezone(&self) -> Option<i16> {
+    self.timezone
+  }
+}
+
+impl PartialOrd for GMonthDay {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    compare_starts(
+      (days_from_civil(1972, self.month, self.day), self.timezone),
+      (
+        days_from_civil(1972, other.month, other.day),
+        other.timezone,
+      ),
+    )
+  }
+}
+
+impl fmt::Display for GMonthDay {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "--{:02}-{:02}", self.month, self.day)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+impl FromStr for GMonthDay {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid gMonthDay {:?}", value);
+    if !value.is_ascii() {
+      return Err(invalid());
+    }
+
+    let (value_part, timezone) = split_timezone(value)?;
+    let value_part = value_part.strip_prefix("--").ok_or_else(invalid)?;
+    let (month, day) = value_part.split_once('-').ok_or_else(invalid)?;
+    let month = parse_digits(month, 2).ok_or_else(invalid)?;
+    let day = parse_digits(day, 2).ok_or_else(invalid)?;
+
+    let month_day = GMonthDay::new(month, day).map_err(|_| invalid())?;
+    Ok(GMonthDay {
+      timezone,
+      ..month_day
+    })
+  }
+}
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+const NANOSECONDS_PER_SECOND: u32 = 1_000_000_000;
+
+/// An `xs:duration` value, e.g. `P1Y2M3DT4H5M6.5S` or `-PT15M`.
+///
+/// A duration is kept as a number of months (years and months) and a number of seconds (days,
+/// hours, minutes and seconds), as the length of a month varies. It is written in its canonical
+/// form: `PT36H` is written back as `P1DT12H`. Seconds are kept to the nanosecond, a value with
+/// more precise fractional seconds is rejected.
+///
+/// Durations only have a partial order: `P1M` is shorter than `P32D`, but not comparable with
+/// `P30D`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Duration {
+  negative: bool,
+  months: u64,
+  seconds: u64,
+  nanoseconds: u32,
+}
+
+impl Duration {
+  pub fn new(months: u64, seconds: u64, nanoseconds: u32) -> Result<Self, String> {
+    if nanoseconds >= NANOSECONDS_PER_SECOND {
+      return Err(format!(
+        "{} nanoseconds is out of the 0..999999999 range",
+        nanoseconds
+      ));
+    }
+
+    Ok(Duration {
+      negative: false,
+      months,
+      seconds,
+      nanoseconds,
+    })
+  }
+
+  /// The same duration, going backwards. A zero duration is never negative.
+  pub fn negate(self) -> Self {
+    Duration {
+      negative: !self.negative && !self.is_zero(),
+      ..self
+    }
+  }
+
+  pub fn is_negative(&self) -> bool {
+    self.negative
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.months == 0 && self.seconds == 0 && self.nanoseconds == 0
+  }
+
+  /// The years and months part, in months.
+  pub fn months(&self) -> u64 {
+    self.months
+  }
+
+  /// The days, hours, minutes and whole seconds part, in seconds.
+  pub fn seconds(&self) -> u64 {
+    self.seconds
+  }
+
+  /// The fractional part of the seconds, in nanoseconds.
+  pub fn subsec_nanos(&self) -> u32 {
+    self.nanoseconds
+  }
+
+  /// The instant reached from the first day of `year` and `month`, in nanoseconds from
+  /// 1970-01-01.
+  fn end_from(&self, year: i32, month: u8) -> i128 {
+    let sign = if self.negative { -1 } else { 1 };
+    let months = sign * i128::from(self.months);
+    let nanoseconds = sign
+      * (i128::from(self.seconds) * i128::from(NANOSECONDS_PER_SECOND)
+        + i128::from(self.nanoseconds));
+
+    // 400 years, or 4800 months, always have the same number of days
+    let cycles = months.div_euclid(4800);
+    let month_index = i32::from(month) - 1 + months.rem_euclid(4800) as i32;
+    let days = days_from_civil(year + month_index / 12, (month_index % 12 + 1) as u8, 1);
+    let days = i128::from(days) + cycles * 146_097;
+
+    days * i128::from(SECONDS_PER_DAY) * i128::from(NANOSECONDS_PER_SECOND) + nanoseconds
+  }
+}
+
+/// The starting instants used by XML Schema to compare durations, chosen so that the number of
+/// days in the following months varies as much as possible.
+const DURATION_REFERENCES: [(i32, u8); 4] = [(1696, 9), (1697, 2), (1903, 3), (1903, 7)];
+
+impl PartialOrd for Duration {
+  /// As in XML Schema, durations are compared on the instants they reach from each reference.
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    if self == other {
+      return Some(Ordering::Equal);
+    }
+
+    let mut orderings = DURATION_REFERENCES.iter().map(|(year, month)| {
+      self
+        .end_from(*year, *month)
+        .cmp(&other.end_from(*year, *month))
+    });
+    let ordering = orderings.next()?;
+
+    if ordering != Ordering::Equal && orderings.all(|other_ordering| other_ordering == ordering) {
+      Some(ordering)
+    } else {
+      None
+    }
+  }
+}
+
+impl fmt::Display for Duration {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.negative {
+      write!(f, "-")?;
+    }
+    write!(f, "P")?;
+
+    if self.is_zero() {
+      return write!(f, "T0S");
+    }
+
+    let years = self.months / 12;
+    let months = self.months % 12;
+    let days = self.seconds / SECONDS_PER_DAY;
+    let hours = self.seconds % SECONDS_PER_DAY / SECONDS_PER_HOUR;
+    let minutes = self.seconds % SECONDS_PER_HOUR / SECONDS_PER_MINUTE;
+    let seconds = self.seconds % SECONDS_PER_MINUTE;
+
+    for (value, designator) in [(years, 'Y'), (months, 'M'), (days, 'D')] {
+      if value != 0 {
+        write!(f, "{}{}", value, designator)?;
+      }
+    }
+
+    if hours == 0 && minutes == 0 && seconds == 0 && self.nanoseconds == 0 {
+      return Ok(());
+    }
+
+    write!(f, "T")?;
+    for (value, designator) in [(hours, 'H'), (minutes, 'M')] {
+      if value != 0 {
+        write!(f, "{}{}", value, designator)?;
+      }
+    }
+
+    if seconds != 0 || self.nanoseconds != 0 {
+      write!(f, "{}", seconds)?;
+      if self.nanoseconds != 0 {
+        let fraction = format!("{:09}", self.nanoseconds);
+        write!(f, ".{}", fraction.trim_end_matches('0'))?;
+      }
+      write!(f, "S")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Split `value` in `(number, designator)` components, with designators in the given order.
+fn duration_components<'a>(value: &'a str, designators: &[char]) -> Option<Vec<(&'a str, char)>> {
+  let mut components = vec![];
+  let mut remaining = value;
+  let mut next_designator = 0;
+
+  while !remaining.is_empty() {
+    let end = remaining.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, rest) = remaining.split_at(end);
+    let designator = rest.chars().next()?;
+    let position = designators[next_designator..]
+      .iter()
+      .position(|d| *d == designator)?;
+
+    components.push((number, designator));
+    next_designator += position + 1;
+    remaining = &rest[1..];
+  }
+
+  Some(components)
+}
+
+impl FromStr for Duration {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid duration {:?}", value);
+    let out_of_range = || format!("Duration {:?} is out of range", value);
+    if !value.is_ascii() {
+      return Err(invalid());
+    }
+
+    let (negative, unsigned) = match value.strip_prefix('-') {
+      Some(unsigned) => (true, unsigned),
+      None => (false, value),
+    };
+    let unsigned = unsigned.strip_prefix('P').ok_or_else(invalid)?;
+    let (date_part, time_part) = match unsigned.split_once('T') {
+      Some((_, "")) => return Err(invalid()),
+      Some((date_part, time_part)) => (date_part, time_part),
+      None => (unsigned, ""),
+    };
+
+    let date_components = duration_components(date_part, &['Y', 'M', 'D']).ok_or_else(invalid)?;
+    let time_components = duration_components(time_part, &['H', 'M', 'S']).ok_or_else(invalid)?;
+    if date_components.is_empty() && time_components.is_empty() {
+      return Err(invalid());
+    }
+
+    let parse_integer = |number: &str| -> Result<u64, String> {
+      if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+      }
+      number.parse().map_err(|_| out_of_range())
+    };
+
+    let mut months = 0u64;
+    let mut seconds = 0u64;
+    let mut nanoseconds = 0u32;
+
+    for (number, designator) in date_components {
+      let number = parse_integer(number)?;
+      let (total, factor) = match designator {
+        'Y' => (&mut months, 12),
+        'M' => (&mut months, 1),
+        _ => (&mut seconds, SECONDS_PER_DAY),
+      };
+      *total = number
+        .checked_mul(factor)
+        .and_then(|value| total.checked_add(value))
+        .ok_or_else(out_of_range)?;
+    }
+
+    for (number, designator) in time_components {
+      let factor = match designator {
+        'H' => SECONDS_PER_HOUR,
+        'M' => SECONDS_PER_MINUTE,
+        _ => {
+          // Either the whole or the fractional part may be empty, as in `5.S` or `.5S`
+          let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+          if whole.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+          }
+          if !fraction.is_empty() {
+            if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+              return Err(invalid());
+            }
+            // Digits past the nanoseconds are accepted as long as no precision is lost
+            let (fraction, rest) = fraction.split_at(fraction.len().min(9));
+            if rest.bytes().any(|b| b != b'0') {
+              return Err(format!(
+                "Duration {:?} is more precise than nanoseconds",
+                value
+              ));
+            }
+            nanoseconds = format!("{:0<9}", fraction).parse().map_err(|_| invalid())?;
+          }
+          if !whole.is_empty() {
+            seconds = seconds
+              .checked_add(parse_integer(whole)?)
+              .ok_or_else(out_of_range)?;
+          }
+          continue;
+        }
+      };
+
+      seconds = parse_integer(number)?
+        .checked_mul(factor)
+        .and_then(|value| seconds.checked_add(value))
+        .ok_or_else(out_of_range)?;
+    }
+
+    let duration = Duration::new(months, seconds, nanoseconds)?;
+    Ok(if negative {
+      duration.negate()
+    } else {
+      duration
+    })
+  }
+}
+
+impl From<std::time::Duration> for Duration {
+  fn from(duration: std::time::Duration) -> Self {
+    Duration {
+      negative: false,
+      months: 0,
+      seconds: duration.as_secs(),
+      nanoseconds: duration.subsec_nanos(),
+    }
+  }
+}
+
+/// Only positive durations without years and months have a fixed length.
+impl TryFrom<Duration> for std::time::Duration {
+  type Error = String;
+
+  fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+    if duration.negative {
+      return Err(format!("Negative duration {} can't be converted", duration));
+    }
+    if duration.months != 0 {
+      return Err(format!(
+        "Duration {} with years or months can't be converted",
+        duration
+      ));
+    }
+
+    Ok(std::time::Duration::new(
+      duration.seconds,
+      duration.nanoseconds,
+    ))
+  }
+}
+
+value_type!(GYear, "gYear", GYear::to_string, GYear::from_str);
+value_type!(
+  GYearMonth,
+  "gYearMonth",
+  GYearMonth::to_string,
+  GYearMonth::from_str
+);
+value_type!(
+  GMonthDay,
+  "gMonthDay",
+  GMonthDay::to_string,
+  GMonthDay::from_str
+);
+value_type!(
+  Duration,
+  "duration",
+  Duration::to_string,
+  Duration::from_str
+);
@@ -0,0 +1,95 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn base64_and_hex_fields() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "certificate")]
+  pub struct Certificate {
+    #[yaserde(attribute = true, hex = true)]
+    fingerprint: [u8; 4],
+    #[yaserde(attribute = true, base64 = true)]
+    salt: Option<Vec<u8>>,
+    #[yaserde(base64 = true, line_length = 8)]
+    data: Vec<u8>,
+    #[yaserde(hex = true)]
+    serial: Option<Box<[u8]>>,
+    #[yaserde(base64 = true)]
+    thumbnail: Option<Vec<u8>>,
+  }
+
+  let model = Certificate {
+    fingerprint: [0xde, 0xad, 0xbe, 0xef],
+    salt: None,
+    data: b"certificate data".to_vec(),
+    serial: Some(vec![0x01, 0xab].into_boxed_slice()),
+    thumbnail: None,
+  };
+  let content = "<certificate fingerprint=\"DEADBEEF\"><data>Y2VydGlm\naWNhdGUg\nZGF0YQ==</data><serial>01AB</serial></certificate>";
+
+  // `serialize_and_validate` drops line breaks from the expected content
+  assert_eq!(
+    yaserde::ser::to_string(&model),
+    Ok(format!(
+      r#"<?xml version="1.0" encoding="utf-8"?>{}"#,
+      content
+    ))
+  );
+  deserialize_and_validate!(content, model, Certificate);
+
+  // Line-wrapped and indented values from other producers, lowercase hexadecimal
+  let content = r#"<certificate fingerprint="deadbeef" salt="AAE=">
+    <data>
+      Y2VydGlmaWNh
+      dGUgZGF0YQ==
+    </data>
+    <thumbnail>iVBORw</thumbnail>
+  </certificate>"#;
+  let loaded: Certificate = yaserde::de::from_str(content).unwrap();
+
+  assert_eq!(loaded.fingerprint, [0xde, 0xad, 0xbe, 0xef]);
+  assert_eq!(loaded.salt, Some(vec![0x00, 0x01]));
+  assert_eq!(loaded.data, b"certificate data");
+  assert_eq!(loaded.serial, None);
+  assert_eq!(loaded.thumbnail, Some(vec![0x89, 0x50, 0x4e, 0x47]));
+
+  let content = r#"<certificate fingerprint="DEADBE"><data>AA==</data></certificate>"#;
+  let loaded: Result<Certificate, String> = yaserde::de::from_str(content);
+  assert_eq!(loaded, Err("Expected 4 bytes, found 3".to_string()));
+
+  let content = r#"<certificate fingerprint="DEADBEEF"><data>A$==</data></certificate>"#;
+  let loaded: Result<Certificate, String> = yaserde::de::from_str(content);
+  assert_eq!(loaded, Err("Invalid base64 value \"A$==\"".to_string()));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn base64_text_content() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "thumbnail")]
+  pub struct Thumbnail {
+    #[yaserde(attribute = true)]
+    format: String,
+    #[yaserde(text = true, base64 = true)]
+    data: bytes::Bytes,
+  }
+
+  let model = Thumbnail {
+    format: "png".to_string(),
+    data: bytes::Bytes::from_static(b"\x89PNG"),
+  };
+  let content = r#"<thumbnail format="png">iVBORw==</thumbnail>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Thumbnail);
+}
@@ -0,0 +1,172 @@
+#![cfg(any(feature = "chrono", feature = "time"))]
+
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_types() {
+  init();
+
+  use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "event")]
+  pub struct Event {
+    #[yaserde(attribute = true)]
+    created: DateTime<Utc>,
+    #[yaserde(attribute = true)]
+    day: Option<NaiveDate>,
+    start: DateTime<FixedOffset>,
+    local: NaiveDateTime,
+    at: Option<NaiveTime>,
+  }
+
+  let model = Event {
+    created: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+    day: Some(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+    start: FixedOffset::east_opt(2 * 3600)
+      .unwrap()
+      .with_ymd_and_hms(2024, 1, 2, 10, 30, 0)
+      .unwrap()
+      + chrono::Duration::milliseconds(250),
+    local: NaiveDate::from_ymd_opt(2024, 1, 2)
+      .unwrap()
+      .and_hms_micro_opt(23, 59, 59, 123_456)
+      .unwrap(),
+    at: Some(NaiveTime::from_hms_opt(8, 0, 0).unwrap()),
+  };
+  let content = r#"<event created="2024-01-02T03:04:05Z" day="2024-01-02"><start>2024-01-02T10:30:00.250+02:00</start><local>2024-01-02T23:59:59.123456</local><at>08:00:00</at></event>"#;
+
+  let start = model.start;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Event);
+
+  // a dateTime without timezone is read as UTC
+  let content = r#"<event created="2024-01-02T03:04:05"><start>2024-01-02T08:30:00.25Z</start><local>2024-01-02T23:59:59.123456</local></event>"#;
+  let loaded: Event = yaserde::de::from_str(content).unwrap();
+  assert_eq!(
+    loaded.created,
+    Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()
+  );
+  assert_eq!(loaded.start, start);
+  assert_eq!(loaded.day, None);
+  assert_eq!(loaded.at, None);
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "event")]
+  pub struct Text {
+    #[yaserde(text = true)]
+    value: NaiveDate,
+  }
+
+  let model = Text {
+    value: NaiveDate::from_ymd_opt(1999, 12, 31).unwrap(),
+  };
+
+  serialize_and_validate!(model, "<event>1999-12-31</event>");
+  deserialize_and_validate!("<event>1999-12-31</event>", model, Text);
+
+  let result: Result<Text, String> = yaserde::de::from_str("<event>31/12/1999</event>");
+  assert!(result
+    .unwrap_err()
+    .starts_with("Invalid date \"31/12/1999\""));
+
+  // the naive types convert a timezone to UTC, a date keeps its day
+  let content = r#"<event created="2002-10-10T12:00:00Z" day="2002-10-10-05:00"><start>2002-10-10T12:00:00Z</start><local>2002-10-10T23:00:00-05:00</local><at>13:20:00+01:00</at></event>"#;
+  let loaded: Event = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.day, NaiveDate::from_ymd_opt(2002, 10, 10));
+  assert_eq!(
+    loaded.local,
+    NaiveDate::from_ymd_opt(2002, 10, 11)
+      .unwrap()
+      .and_hms_opt(4, 0, 0)
+      .unwrap()
+  );
+  assert_eq!(loaded.at, NaiveTime::from_hms_opt(12, 20, 0));
+
+  let loaded: Text = yaserde::de::from_str("<event>1999-12-31Z</event>").unwrap();
+  assert_eq!(loaded.value, NaiveDate::from_ymd_opt(1999, 12, 31).unwrap());
+
+  let result: Result<Text, String> = yaserde::de::from_str("<event>1999-12-31+15:00</event>");
+  assert_eq!(
+    result,
+    Err("Invalid timezone in \"1999-12-31+15:00\"".to_string())
+  );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_types() {
+  init();
+
+  use time::macros::{date, datetime, time};
+  use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "event")]
+  pub struct Event {
+    #[yaserde(attribute = true)]
+    created: OffsetDateTime,
+    #[yaserde(attribute = true)]
+    day: Option<Date>,
+    start: OffsetDateTime,
+    local: PrimitiveDateTime,
+    at: Option<Time>,
+  }
+
+  let model = Event {
+    created: datetime!(2024-01-02 03:04:05 UTC),
+    day: Some(date!(2024 - 01 - 02)),
+    start: datetime!(2024-01-02 10:30:00.25 -05:30),
+    local: datetime!(2024-01-02 23:59:59.123456),
+    at: Some(time!(08:00:00)),
+  };
+  let content = r#"<event created="2024-01-02T03:04:05Z" day="2024-01-02"><start>2024-01-02T10:30:00.25-05:30</start><local>2024-01-02T23:59:59.123456</local><at>08:00:00</at></event>"#;
+
+  let start = model.start;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Event);
+
+  // a dateTime without timezone is read as UTC
+  let content = r#"<event created="2024-01-02T03:04:05"><start>2024-01-02T16:00:00.250Z</start><local>2024-01-02T23:59:59.123456</local></event>"#;
+  let loaded: Event = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.created, datetime!(2024-01-02 03:04:05 UTC));
+  assert_eq!(loaded.start, start);
+  assert_eq!(loaded.day, None);
+  assert_eq!(loaded.at, None);
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "event")]
+  pub struct Text {
+    #[yaserde(text = true)]
+    value: Time,
+  }
+
+  let model = Text {
+    value: time!(12:34:56.5),
+  };
+
+  serialize_and_validate!(model, "<event>12:34:56.5</event>");
+  deserialize_and_validate!("<event>12:34:56.5</event>", model, Text);
+
+  // a timezone is converted to UTC, a date keeps its day
+  let loaded: Text = yaserde::de::from_str("<event>13:34:56.5+01:00</event>").unwrap();
+  assert_eq!(loaded.value, time!(12:34:56.5));
+  let loaded: Text = yaserde::de::from_str("<event>12:34:56.5Z</event>").unwrap();
+  assert_eq!(loaded.value, time!(12:34:56.5));
+
+  let content = r#"<event created="2002-10-10T12:00:00Z" day="2002-10-10Z"><start>2002-10-10T12:00:00Z</start><local>2002-10-10T00:30:00+01:00</local><at>23:20:00-02:00</at></event>"#;
+  let loaded: Event = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded.day, Some(date!(2002 - 10 - 10)));
+  assert_eq!(loaded.local, datetime!(2002-10-09 23:30:00));
+  assert_eq!(loaded.at, Some(time!(01:20:00)));
+}
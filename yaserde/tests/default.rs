@@ -148,3 +148,182 @@ fn module_inclusion() {
   serialize_and_validate!(model, content);
   deserialize_and_validate!(content, model, XmlStruct);
 }
+
+mod defaults {
+  pub fn port() -> u16 {
+    8080
+  }
+}
+
+#[test]
+fn default_field_trait() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(attribute = true, default)]
+    count: u32,
+    #[yaserde(default)]
+    label: String,
+  }
+
+  let content = "<base />";
+  let model = XmlStruct {
+    count: 0,
+    label: String::new(),
+  };
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+
+  let content = r#"<base count="2"><label>ready</label></base>"#;
+  let model = XmlStruct {
+    count: 2,
+    label: "ready".to_string(),
+  };
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+}
+
+#[test]
+fn default_field_path() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(default = "crate::defaults::port")]
+    port: u16,
+    #[yaserde(attribute = true, default = "Self::default_retries")]
+    retries: u8,
+  }
+
+  impl XmlStruct {
+    fn default_retries() -> u8 {
+      3
+    }
+  }
+
+  let content = "<base />";
+  let model = XmlStruct {
+    port: 8080,
+    retries: 3,
+  };
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+
+  let content = r#"<base retries="1"><port>80</port></base>"#;
+  let model = XmlStruct {
+    port: 80,
+    retries: 1,
+  };
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+}
+
+#[test]
+fn default_field_vec() {
+  init();
+
+  fn default_items() -> Vec<String> {
+    vec!["first".to_string(), "second".to_string()]
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(default = "default_items")]
+    items: Vec<String>,
+  }
+
+  // the default applies when no item is read
+  let loaded: Result<XmlStruct, String> = yaserde::de::from_str("<base />");
+  assert_eq!(
+    loaded,
+    Ok(XmlStruct {
+      items: default_items()
+    })
+  );
+
+  let content = "<base><items>third</items></base>";
+  let model = XmlStruct {
+    items: vec!["third".to_string()],
+  };
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+}
+
+#[test]
+fn default_container() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "server", default)]
+  pub struct Server {
+    #[yaserde(attribute = true)]
+    host: String,
+    port: u16,
+    #[yaserde(default = "crate::defaults::port")]
+    admin_port: u16,
+    comment: Option<String>,
+    alias: Vec<String>,
+  }
+
+  impl Default for Server {
+    fn default() -> Self {
+      Server {
+        host: "localhost".to_string(),
+        port: 80,
+        admin_port: 0,
+        comment: Some("default server".to_string()),
+        alias: vec!["local".to_string()],
+      }
+    }
+  }
+
+  let content = r#"<server><port>8000</port></server>"#;
+  let model = Server {
+    host: "localhost".to_string(),
+    port: 8000,
+    admin_port: 8080,
+    comment: Some("default server".to_string()),
+    alias: vec!["local".to_string()],
+  };
+  deserialize_and_validate!(content, model, Server);
+
+  let content = r#"<server host="example.org"><comment>main</comment><alias>www</alias></server>"#;
+  let model = Server {
+    host: "example.org".to_string(),
+    port: 80,
+    admin_port: 8080,
+    comment: Some("main".to_string()),
+    alias: vec!["www".to_string()],
+  };
+  deserialize_and_validate!(content, model, Server);
+}
+
+#[test]
+fn default_container_path() {
+  init();
+
+  fn default_settings() -> Settings {
+    Settings {
+      level: 5,
+      name: "settings".to_string(),
+    }
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "settings", default = "default_settings")]
+  pub struct Settings {
+    level: u8,
+    name: String,
+  }
+
+  let content = "<settings><name>custom</name></settings>";
+  let model = Settings {
+    level: 5,
+    name: "custom".to_string(),
+  };
+  deserialize_and_validate!(content, model, Settings);
+}
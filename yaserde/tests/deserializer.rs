@@ -1071,7 +1071,7 @@ fn de_same_field_name_but_some_other_fields_or_something() {
 fn de_attribute_sequence() {
   init();
 
-  #[derive(Default, Debug, YaDeserialize, PartialEq)]
+  #[derive(Default, Debug, YaDeserialize, YaSerialize, PartialEq)]
   pub enum Inner {
     #[default]
     #[yaserde(rename = "foo")]
@@ -1080,7 +1080,7 @@ fn de_attribute_sequence() {
     Bar,
   }
 
-  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
   pub struct Outer {
     #[yaserde(attribute = true, rename = "seq1")]
     seq1: Vec<i32>,
@@ -1097,10 +1097,38 @@ fn de_attribute_sequence() {
     seq3: vec!["one".to_string(), "two".to_string()],
   };
 
-  //serialize_and_validate!(model, content);
+  serialize_and_validate!(model, content);
   deserialize_and_validate!(content, model, Outer);
 }
 
+#[test]
+fn de_optional_attribute_sequence() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  pub struct Outer {
+    #[yaserde(attribute = true)]
+    seq: Option<Vec<u32>>,
+  }
+
+  let content = r#"<Outer seq="1 2 3" />"#;
+  let model = Outer {
+    seq: Some(vec![1, 2, 3]),
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Outer);
+
+  let content = "<Outer />";
+  let model = Outer { seq: None };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Outer);
+
+  let model = Outer { seq: Some(vec![]) };
+  serialize_and_validate!(model, content);
+}
+
 #[test]
 fn de_nested_macro_rules() {
   init();
@@ -193,18 +193,10 @@ fn attribute_enum2() {
   #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
   #[yaserde(rename = "base")]
   enum Base2 {
-    #[yaserde(flatten = true)]
+    #[yaserde(flatten = true, rename = "child1")]
     C1(Child1),
   }
 
-  impl Default for Base2 {
-    fn default() -> Base2 {
-      Base2::C1(Child1 {
-        val: "hello world".into(),
-      })
-    }
-  }
-
   let content = r#"<base><child1 val="hello world" /></base>"#;
   let model = Base2::C1(Child1 {
     val: "hello world".into(),
@@ -394,3 +386,481 @@ fn tagged_enum() {
   serialize_and_validate!(model, content);
   deserialize_and_validate!(content, model, XmlStruct);
 }
+
+#[test]
+fn namespaced_tagged_enum() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(
+    tag = "xsi:type",
+    rename = "shape",
+    namespaces = {
+      "xsi" = "http://www.w3.org/2001/XMLSchema-instance"
+    }
+  )]
+  enum Shape {
+    #[default]
+    #[yaserde(rename = "circle")]
+    Circle,
+    #[yaserde(rename = "square")]
+    Square,
+  }
+
+  let model = Shape::Square;
+  let content =
+    r#"<shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="square" />"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Shape);
+
+  // an unprefixed type attribute, or one of another namespace, is not the tag
+  let content = r#"<shape xmlns:other="http://www.sample.com/ns/other" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" type="circle" other:type="circle" xsi:type="square" />"#;
+  let loaded: Result<Shape, String> = yaserde::de::from_str(content);
+  assert_eq!(loaded, Ok(Shape::Square));
+
+  let loaded: Result<Shape, String> = yaserde::de::from_str(r#"<shape type="square" />"#);
+  assert!(loaded
+    .unwrap_err()
+    .starts_with("Expected enum tagged with xsi:type"));
+}
+
+#[test]
+fn untagged_enum() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "point")]
+  struct Point {
+    #[yaserde(attribute = true)]
+    x: i32,
+    #[yaserde(attribute = true)]
+    y: i32,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(untagged = true)]
+  enum Size {
+    #[default]
+    #[yaserde(rename = "auto")]
+    Auto,
+    Value(u32),
+    Ratio(f64),
+    Anchor(Point),
+    Other(String),
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "base")]
+  struct XmlStruct {
+    size: Vec<Size>,
+    #[yaserde(attribute = true)]
+    fallback: Size,
+  }
+
+  let model = XmlStruct {
+    size: vec![
+      Size::Auto,
+      Size::Value(42),
+      Size::Ratio(1.5),
+      Size::Anchor(Point { x: 1, y: 2 }),
+      Size::Other("a & b".to_string()),
+    ],
+    fallback: Size::Value(7),
+  };
+  let content = r#"
+    <base fallback="7">
+      <size>auto</size>
+      <size>42</size>
+      <size>1.5</size>
+      <size x="1" y="2" />
+      <size>a &amp; b</size>
+    </base>
+  "#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  #[yaserde(rename = "value", untagged = true)]
+  enum Strict {
+    Flag(bool),
+    Count(u8),
+  }
+
+  let loaded: Result<Strict, String> = yaserde::de::from_str("<value>300</value>");
+  assert_eq!(
+    loaded,
+    Err(
+      "no variant of untagged enum Strict matched: \
+       Flag: provided string was not `true` or `false`; \
+       Count: number too large to fit in target type"
+        .to_string()
+    )
+  );
+}
+
+#[test]
+fn adjacently_tagged_enum() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "params")]
+  struct Resize {
+    #[yaserde(attribute = true)]
+    unit: String,
+    width: u32,
+    height: u32,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(
+    rename = "action",
+    tag = "type",
+    content = "params",
+    tag_kind = "element"
+  )]
+  enum Action {
+    #[default]
+    #[yaserde(rename = "reset")]
+    Reset,
+    #[yaserde(rename = "resize")]
+    Resize(Resize),
+    #[yaserde(rename = "rotate")]
+    Rotate(f64),
+    #[yaserde(rename = "label")]
+    Label(Option<String>),
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "script")]
+  struct Script {
+    action: Vec<Action>,
+  }
+
+  let model = Script {
+    action: vec![
+      Action::Reset,
+      Action::Resize(Resize {
+        unit: "px".to_string(),
+        width: 10,
+        height: 20,
+      }),
+      Action::Rotate(90.5),
+      Action::Label(Some("a & b".to_string())),
+      Action::Label(None),
+    ],
+  };
+  let content = r#"
+    <script>
+      <action><type>reset</type></action>
+      <action><type>resize</type><params unit="px"><width>10</width><height>20</height></params></action>
+      <action><type>rotate</type><params>90.5</params></action>
+      <action><type>label</type><params>a &amp; b</params></action>
+      <action><type>label</type></action>
+    </script>
+  "#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Script);
+
+  let content = r#"
+    <script>
+      <action><params>90.5</params><comment>ignored</comment><type>rotate</type></action>
+    </script>
+  "#;
+  let model = Script {
+    action: vec![Action::Rotate(90.5)],
+  };
+  deserialize_and_validate!(content, model, Script);
+
+  let loaded: Result<Action, String> = yaserde::de::from_str("<action><type>fly</type></action>");
+  assert_eq!(
+    loaded,
+    Err("Unknown variant \"fly\" for enum Action".to_string())
+  );
+
+  let loaded: Result<Action, String> =
+    yaserde::de::from_str("<action><type>rotate</type></action>");
+  assert_eq!(loaded, Err("Missing params for variant rotate".to_string()));
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "action", tag = "type", content = "value")]
+  enum Setting {
+    #[default]
+    #[yaserde(rename = "none")]
+    Nothing,
+    #[yaserde(rename = "count")]
+    Count(u32),
+  }
+
+  let model = Setting::Count(3);
+  let content = r#"<action type="count"><value>3</value></action>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Setting);
+
+  let model = Setting::Nothing;
+  let content = r#"<action type="none" />"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Setting);
+}
+
+#[test]
+fn namespaced_adjacently_tagged_enum() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(
+    rename = "setting",
+    prefix = "ns",
+    namespaces = {
+      "ns" = "http://www.sample.com/ns/domain",
+      "other" = "http://www.sample.com/ns/other",
+    },
+    tag = "ns:type",
+    content = "ns:value",
+    tag_kind = "element"
+  )]
+  enum Setting {
+    #[default]
+    #[yaserde(rename = "none")]
+    Nothing,
+    #[yaserde(rename = "count")]
+    Count(u32),
+  }
+
+  let model = Setting::Count(3);
+  let content = r#"<ns:setting xmlns:ns="http://www.sample.com/ns/domain" xmlns:other="http://www.sample.com/ns/other"><ns:type>count</ns:type><ns:value>3</ns:value></ns:setting>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Setting);
+
+  // the tag and content elements of another namespace are skipped
+  let content = r#"
+    <ns:setting xmlns:ns="http://www.sample.com/ns/domain" xmlns:other="http://www.sample.com/ns/other">
+      <ns:type>count</ns:type>
+      <ns:value>3</ns:value>
+      <other:type>none</other:type>
+      <other:value>4</other:value>
+    </ns:setting>
+  "#;
+  let model = Setting::Count(3);
+  deserialize_and_validate!(content, model, Setting);
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(
+    rename = "action",
+    namespaces = {
+      "ns" = "http://www.sample.com/ns/domain",
+    },
+    tag = "ns:type",
+    content = "value"
+  )]
+  enum Action {
+    #[default]
+    #[yaserde(rename = "none")]
+    Nothing,
+    #[yaserde(rename = "count")]
+    Count(u32),
+  }
+
+  let model = Action::Count(3);
+  let content = r#"<action xmlns:ns="http://www.sample.com/ns/domain" ns:type="count"><value>3</value></action>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Action);
+
+  // an unprefixed type attribute is not the tag
+  let loaded: Result<Action, String> =
+    yaserde::de::from_str(r#"<action type="count"><value>3</value></action>"#);
+  assert_eq!(loaded, Err("Expected enum tagged with ns:type".to_string()));
+}
+
+#[test]
+fn value_enum() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  enum Access {
+    #[yaserde(rename = "read-only")]
+    Read,
+    #[yaserde(rename = "read-write")]
+    Write,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  #[yaserde(rename = "register")]
+  struct Register {
+    #[yaserde(attribute = true)]
+    access: Access,
+    #[yaserde(attribute = true, rename = "reset-access")]
+    reset_access: Option<Access>,
+    #[yaserde(attribute = true)]
+    modes: Vec<Access>,
+    #[yaserde(text = true)]
+    default_access: Access,
+  }
+
+  let model = Register {
+    access: Access::Read,
+    reset_access: Some(Access::Write),
+    modes: vec![Access::Read, Access::Write],
+    default_access: Access::Write,
+  };
+  let content = r#"<register access="read-only" reset-access="read-write" modes="read-only read-write">read-write</register>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Register);
+
+  let model = Access::Write;
+  serialize_and_validate!(model, "<Access>read-write</Access>");
+  deserialize_and_validate!("<Access>read-write</Access>", model, Access);
+
+  let loaded: Result<Register, String> =
+    yaserde::de::from_str(r#"<register access="write-only">read-only</register>"#);
+  assert_eq!(
+    loaded,
+    Err(
+      "Unknown value \"write-only\" for enum Access, expected one of: read-only, read-write"
+        .to_string()
+    )
+  );
+}
+
+#[test]
+fn other_variant() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  enum Access {
+    #[yaserde(rename = "read-only")]
+    Read,
+    #[yaserde(other = true)]
+    Unknown(String),
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  enum Usage {
+    #[yaserde(rename = "read")]
+    Read,
+    #[yaserde(other = true)]
+    Unknown,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  #[yaserde(rename = "register")]
+  struct Register {
+    #[yaserde(attribute = true)]
+    access: Access,
+    #[yaserde(attribute = true)]
+    usage: Usage,
+    #[yaserde(text = true)]
+    reset_access: Access,
+  }
+
+  let model = Register {
+    access: Access::Unknown("write-once".to_string()),
+    usage: Usage::Read,
+    reset_access: Access::Read,
+  };
+  let content = r#"<register access="write-once" usage="read">read-only</register>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Register);
+
+  let model = Register {
+    access: Access::Read,
+    usage: Usage::Unknown,
+    reset_access: Access::Unknown("none".to_string()),
+  };
+  let content = r#"<register access="read-only" usage="write">none</register>"#;
+  deserialize_and_validate!(content, model, Register);
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "shape")]
+  enum Shape {
+    #[default]
+    Circle,
+    Square(u32),
+    #[yaserde(other = true)]
+    Unknown(String),
+  }
+
+  let model = Shape::Unknown("Hexagon".to_string());
+  serialize_and_validate!(model, "<shape>Hexagon</shape>");
+  deserialize_and_validate!("<shape>Hexagon</shape>", model, Shape);
+
+  // an unknown element is kept as written
+  let model = Shape::Unknown("<Hexagon />".to_string());
+  serialize_and_validate!(model, "<shape><Hexagon /></shape>");
+  deserialize_and_validate!("<shape><Hexagon /></shape>", model, Shape);
+
+  let content = r#"<shape><ns:hexagon xmlns:ns="http://www.sample.com/ns/domain" side="2"><color>red</color></ns:hexagon></shape>"#;
+  let model = Shape::Unknown(
+    r#"<ns:hexagon xmlns:ns="http://www.sample.com/ns/domain" side="2"><color>red</color></ns:hexagon>"#
+      .to_string(),
+  );
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Shape);
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "shape")]
+  enum OpenShape {
+    #[default]
+    Circle,
+    Square(u32),
+    #[yaserde(other = true)]
+    Unknown,
+  }
+
+  let model = OpenShape::Unknown;
+  deserialize_and_validate!("<shape><Hexagon /></shape>", model, OpenShape);
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize, Default)]
+  #[yaserde(rename = "shape", tag = "type")]
+  enum TaggedShape {
+    #[default]
+    #[yaserde(rename = "circle")]
+    Circle,
+    #[yaserde(other = true)]
+    Unknown(String),
+  }
+
+  let model = TaggedShape::Unknown("hexagon".to_string());
+  let content = r#"<shape type="hexagon" />"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, TaggedShape);
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  #[yaserde(
+    rename = "action",
+    tag = "type",
+    content = "params",
+    tag_kind = "element"
+  )]
+  enum Action {
+    #[yaserde(rename = "rotate")]
+    Rotate(f64),
+    #[yaserde(other = true)]
+    Unknown(String),
+  }
+
+  let model = Action::Unknown("fly".to_string());
+  serialize_and_validate!(model, "<action><type>fly</type></action>");
+  deserialize_and_validate!(
+    "<action><type>fly</type><params>high</params></action>",
+    model,
+    Action
+  );
+}
+
+#[test]
+fn enum_without_default() {
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  #[yaserde(rename = "color")]
+  enum Color {
+    White,
+    Custom(u32),
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize, YaDeserialize)]
+  #[yaserde(rename = "base")]
+  struct XmlStruct {
+    color: Color,
+  }
+
+  let model = XmlStruct {
+    color: Color::Custom(7),
+  };
+  let content = "<base><color><Custom>7</Custom></color></base>";
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+
+  let loaded: Result<XmlStruct, String> =
+    yaserde::de::from_str("<base><color>Purple</color></base>");
+  assert_eq!(
+    loaded,
+    Err("No variant of enum Color matched in \"color\"".to_string())
+  );
+}
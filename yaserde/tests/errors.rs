@@ -44,3 +44,268 @@ fn de_wrong_end_balise() {
     Err("Unexpected closing tag: book != author".to_owned())
   );
 }
+
+#[test]
+fn de_deny_unknown_fields() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "config", deny_unknown_fields)]
+  pub struct Config {
+    #[yaserde(attribute = true)]
+    name: String,
+    #[yaserde(alias = "delay")]
+    timeout: u32,
+    server: Server,
+  }
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "server")]
+  pub struct Server {
+    host: String,
+  }
+
+  let content = r#"<config name="main"><delay>3</delay><server><host>localhost</host><port>80</port></server></config>"#;
+  let loaded: Result<Config, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Ok(Config {
+      name: "main".to_string(),
+      timeout: 3,
+      server: Server {
+        host: "localhost".to_string()
+      },
+    })
+  );
+
+  let content = r#"<config name="main"><timout>3</timout></config>"#;
+  let loaded: Result<Config, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Err(
+      "Unexpected element <timout> in Config, expected one of: timeout, delay, server".to_owned()
+    )
+  );
+
+  let content = r#"<config name="main" mode="fast"><timeout>3</timeout></config>"#;
+  let loaded: Result<Config, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Err("Unexpected attribute mode in Config, expected one of: name".to_owned())
+  );
+
+  let content = r#"<config name="main">text<timeout>3</timeout></config>"#;
+  let loaded: Result<Config, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Err("Unexpected text content \"text\" in Config".to_owned())
+  );
+}
+
+#[test]
+fn de_deny_unknown_fields_config() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "server")]
+  pub struct Server {
+    host: String,
+  }
+
+  let content = "<server><host>localhost</host><port>80</port></server>";
+  let loaded: Result<Server, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Ok(Server {
+      host: "localhost".to_string()
+    })
+  );
+
+  let config = yaserde::de::Config {
+    deny_unknown_fields: true,
+    ..Default::default()
+  };
+  let loaded: Result<Server, String> = yaserde::de::from_str_with_config(content, &config);
+  assert_eq!(
+    loaded,
+    Err("Unexpected element <port> in Server, expected one of: host".to_owned())
+  );
+}
+
+#[test]
+fn de_deny_unknown_fields_config_with_flatten() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "node")]
+  pub struct Node {
+    #[yaserde(flatten = true)]
+    base: Base,
+    #[yaserde(attribute = true)]
+    kind: String,
+  }
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  pub struct Base {
+    name: String,
+    detail: Detail,
+  }
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  pub struct Detail {
+    size: u32,
+  }
+
+  let config = yaserde::de::Config {
+    deny_unknown_fields: true,
+    ..Default::default()
+  };
+
+  let content = r#"<node kind="leaf"><name>n1</name><detail><size>3</size></detail></node>"#;
+  let loaded: Result<Node, String> = yaserde::de::from_str_with_config(content, &config);
+  assert_eq!(
+    loaded,
+    Ok(Node {
+      base: Base {
+        name: "n1".to_string(),
+        detail: Detail { size: 3 },
+      },
+      kind: "leaf".to_string(),
+    })
+  );
+
+  // the flattened field is read with the same config
+  let content =
+    r#"<node kind="leaf"><name>n1</name><detail><size>3</size><color>red</color></detail></node>"#;
+  let loaded: Result<Node, String> = from_str(content);
+  assert!(loaded.is_ok());
+  let loaded: Result<Node, String> = yaserde::de::from_str_with_config(content, &config);
+  assert_eq!(
+    loaded,
+    Err("Unexpected element <color> in Detail, expected one of: size".to_owned())
+  );
+
+  // a child element of the struct is checked against the flattened fields
+  let content =
+    r#"<node kind="leaf"><name>n1</name><detail><size>3</size></detail><size>3</size></node>"#;
+  let loaded: Result<Node, String> = from_str(content);
+  assert!(loaded.is_ok());
+  let loaded: Result<Node, String> = yaserde::de::from_str_with_config(content, &config);
+  assert_eq!(
+    loaded,
+    Err("Unexpected element <size> in Node, expected one of: an element of Base".to_owned())
+  );
+}
+
+#[test]
+fn de_deny_unknown_fields_in_wrapper() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "peripheral", deny_unknown_fields)]
+  pub struct Peripheral {
+    #[yaserde(rename = "register", wrapper = "registers")]
+    registers: Vec<String>,
+  }
+
+  let content = "<peripheral><registers><register>CR1</register></registers></peripheral>";
+  let loaded: Result<Peripheral, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Ok(Peripheral {
+      registers: vec!["CR1".to_string()],
+    })
+  );
+
+  let content =
+    "<peripheral><registers><register>CR1</register><field>EN</field></registers></peripheral>";
+  let loaded: Result<Peripheral, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Err(
+      "Unexpected element <field> in registers of Peripheral, expected one of: register".to_owned()
+    )
+  );
+}
+
+#[test]
+fn de_deny_unknown_fields_with_flatten() {
+  init();
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "node", deny_unknown_fields)]
+  pub struct Node {
+    #[yaserde(flatten = true)]
+    base: Base,
+    #[yaserde(attribute = true)]
+    kind: String,
+  }
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  pub struct Base {
+    #[yaserde(attribute = true)]
+    id: String,
+    #[yaserde(flatten = true)]
+    label: Label,
+  }
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  pub struct Label {
+    #[yaserde(attribute = true)]
+    lang: String,
+  }
+
+  let content = r#"<node id="n1" kind="leaf" lang="en" />"#;
+  let loaded: Result<Node, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Ok(Node {
+      base: Base {
+        id: "n1".to_string(),
+        label: Label {
+          lang: "en".to_string(),
+        },
+      },
+      kind: "leaf".to_string(),
+    })
+  );
+
+  let content = r#"<node id="n1" kind="leaf" lang="en" color="red" />"#;
+  let loaded: Result<Node, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Err(
+      "Unexpected attribute color in Node, expected one of: kind, an attribute of Base".to_owned()
+    )
+  );
+
+  let content = r#"<node id="n1" kind="leaf" lang="en">text</node>"#;
+  let loaded: Result<Node, String> = from_str(content);
+  assert_eq!(
+    loaded,
+    Err("Unexpected text content \"text\" in Node".to_owned())
+  );
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  #[yaserde(rename = "note", deny_unknown_fields)]
+  pub struct Note {
+    #[yaserde(flatten = true)]
+    body: Body,
+  }
+
+  #[derive(YaDeserialize, PartialEq, Debug)]
+  pub struct Body {
+    #[yaserde(text = true)]
+    text: String,
+  }
+
+  let loaded: Result<Note, String> = from_str("<note>text</note>");
+  assert_eq!(
+    loaded,
+    Ok(Note {
+      body: Body {
+        text: "text".to_string(),
+      },
+    })
+  );
+}
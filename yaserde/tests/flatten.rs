@@ -269,6 +269,6 @@ fn flatten_name_in_unknown_child() {
   let content = r#"<Node id="Foo"><value><Foo /></value></Node>"#;
   serialize_and_validate!(model, content);
 
-  let content = r#"<Node id="Foo"><value><SomethingThatDoesntExist><value></value></SomethingThatDoesntExist></value></Node>"#;
+  let content = r#"<Node id="Foo"><value><SomethingThatDoesntExist><value></value></SomethingThatDoesntExist><Foo /></value></Node>"#;
   deserialize_and_validate!(content, model, Node);
 }
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate yaserde;
 
+use std::marker::PhantomData;
 use yaserde::{YaDeserialize, YaSerialize};
 
 fn init() {
@@ -15,12 +16,12 @@ fn generic() {
   #[yaserde(rename = "base")]
   pub struct Base<G>
   where
-    G: YaSerialize + YaDeserialize + Default,
+    G: YaSerialize + YaDeserialize,
   {
     background: G,
   }
 
-  #[derive(Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
   pub struct Generic {
     #[yaserde(attribute = true)]
     color: String,
@@ -39,3 +40,28 @@ fn generic() {
   let loaded: Result<Base<Generic>, String> = yaserde::de::from_str(content);
   assert_eq!(loaded, Ok(model));
 }
+
+#[test]
+fn lifetime_parameter() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "base")]
+  pub struct Base<'a> {
+    #[yaserde(attribute = true)]
+    color: String,
+    name: String,
+    #[yaserde(skip = true)]
+    source: PhantomData<&'a str>,
+  }
+
+  let content = r#"<base color="blue"><name>sky</name></base>"#;
+  let model = Base {
+    color: "blue".to_string(),
+    name: "sky".to_string(),
+    source: PhantomData,
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Base);
+}
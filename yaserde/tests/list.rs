@@ -0,0 +1,322 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn attribute_list_with_separator() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "shape")]
+  pub struct Shape {
+    #[yaserde(attribute = true, separator = ",")]
+    class: Vec<String>,
+    #[yaserde(attribute = true, separator = ";")]
+    points: Option<Vec<u32>>,
+  }
+
+  let content = r#"<shape class="a,b c" points="1;2;3" />"#;
+  let model = Shape {
+    class: vec!["a".to_string(), "b c".to_string()],
+    points: Some(vec![1, 2, 3]),
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Shape);
+
+  let content = r#"<shape class="" />"#;
+  let model = Shape {
+    class: vec![],
+    points: None,
+  };
+
+  serialize_and_validate!(model, "<shape />");
+  deserialize_and_validate!(content, model, Shape);
+
+  // without trim, empty items are kept
+  let content = r#"<shape class="a,,b," />"#;
+  let model = Shape {
+    class: vec![
+      "a".to_string(),
+      String::new(),
+      "b".to_string(),
+      String::new(),
+    ],
+    points: None,
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Shape);
+}
+
+#[test]
+fn attribute_list_with_trimmed_separator() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "shape")]
+  pub struct Shape {
+    #[yaserde(attribute = true, separator = ",", trim = true)]
+    points: Vec<f64>,
+  }
+
+  let model = Shape {
+    points: vec![1.5, 2.0, 3.25],
+  };
+
+  serialize_and_validate!(model, r#"<shape points="1.5,2,3.25" />"#);
+  deserialize_and_validate!(r#"<shape points=" 1.5, 2 ,3.25, " />"#, model, Shape);
+}
+
+#[test]
+fn text_list() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "values")]
+  pub struct Values {
+    #[yaserde(text = true)]
+    items: Vec<i32>,
+  }
+
+  let content = "<values>1 -2 3</values>";
+  let model = Values {
+    items: vec![1, -2, 3],
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Values);
+
+  let model = Values { items: vec![] };
+
+  serialize_and_validate!(model, "<values />");
+  deserialize_and_validate!("<values />", model, Values);
+}
+
+#[test]
+fn text_list_with_separator() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "tags")]
+  pub struct Tags {
+    #[yaserde(attribute = true)]
+    kind: String,
+    #[yaserde(text = true, separator = "|", trim = true)]
+    items: Vec<String>,
+  }
+
+  let model = Tags {
+    kind: "labels".to_string(),
+    items: vec!["red".to_string(), "dark blue".to_string()],
+  };
+
+  serialize_and_validate!(model, r#"<tags kind="labels">red|dark blue</tags>"#);
+  deserialize_and_validate!(r#"<tags kind="labels">red | dark blue</tags>"#, model, Tags);
+}
+
+#[test]
+fn text_list_of_enums() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  pub enum Day {
+    #[yaserde(rename = "mon")]
+    Monday,
+    #[yaserde(rename = "tue")]
+    Tuesday,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "days")]
+  pub struct Days {
+    #[yaserde(text = true, separator = ",")]
+    items: Vec<Day>,
+  }
+
+  let content = "<days>tue,mon</days>";
+  let model = Days {
+    items: vec![Day::Tuesday, Day::Monday],
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Days);
+
+  let loaded: Result<Days, String> = yaserde::de::from_str("<days>mon,wed</days>");
+  assert_eq!(
+    loaded,
+    Err("Unknown value \"wed\" for enum Day, expected one of: mon, tue".to_string())
+  );
+}
+
+#[test]
+fn wrapped_list() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "register")]
+  pub struct Register {
+    #[yaserde(attribute = true)]
+    name: String,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "peripheral")]
+  pub struct Peripheral {
+    #[yaserde(rename = "register", wrapper = "registers")]
+    registers: Vec<Register>,
+    #[yaserde(rename = "value", wrapper = "values")]
+    values: Vec<u32>,
+    #[yaserde(rename = "item", wrapper = "items", emit_empty_wrapper = true)]
+    items: Vec<String>,
+  }
+
+  let content = r#"
+    <peripheral>
+      <registers>
+        <register name="CR1" />
+        <register name="CR2" />
+      </registers>
+      <values>
+        <value>1</value>
+        <value>2</value>
+      </values>
+      <items />
+    </peripheral>
+  "#;
+  let model = Peripheral {
+    registers: vec![
+      Register {
+        name: "CR1".to_string(),
+      },
+      Register {
+        name: "CR2".to_string(),
+      },
+    ],
+    values: vec![1, 2],
+    items: vec![],
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Peripheral);
+
+  let model = Peripheral {
+    registers: vec![],
+    values: vec![],
+    items: vec!["a".to_string()],
+  };
+  let content = "<peripheral><items><item>a</item></items></peripheral>";
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Peripheral);
+
+  let content = r#"
+    <peripheral>
+      <registers>
+        <unknown><register name="ignored" /></unknown>
+        <register name="CR1" />
+      </registers>
+      <register name="ignored" />
+    </peripheral>
+  "#;
+  let model = Peripheral {
+    registers: vec![Register {
+      name: "CR1".to_string(),
+    }],
+    values: vec![],
+    items: vec![],
+  };
+
+  deserialize_and_validate!(content, model, Peripheral);
+}
+
+#[test]
+fn namespaced_wrapped_list() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "list",
+    prefix = "ns",
+    namespaces = {
+      "ns" = "http://www.sample.com/ns/domain",
+      "other" = "http://www.sample.com/ns/other",
+    }
+  )]
+  pub struct List {
+    #[yaserde(prefix = "ns", rename = "item", wrapper = "items")]
+    items: Vec<String>,
+    #[yaserde(prefix = "ns", rename = "item", wrapper = "other:items")]
+    other_items: Vec<String>,
+  }
+
+  let content = r#"
+    <ns:list xmlns:ns="http://www.sample.com/ns/domain" xmlns:other="http://www.sample.com/ns/other">
+      <ns:items><ns:item>a</ns:item></ns:items>
+      <other:items><ns:item>b</ns:item></other:items>
+    </ns:list>
+  "#;
+  let model = List {
+    items: vec!["a".to_string()],
+    other_items: vec!["b".to_string()],
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, List);
+}
+
+#[test]
+fn element_list() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "series")]
+  pub struct Series {
+    #[yaserde(list = true)]
+    values: Vec<f64>,
+    #[yaserde(list = true)]
+    counts: Option<Vec<u32>>,
+    #[yaserde(list = true, separator = ",")]
+    labels: Vec<String>,
+  }
+
+  let content = r#"
+    <series>
+      <values>1 2.5 3.7</values>
+      <counts>4 5</counts>
+      <labels>a,b c</labels>
+    </series>
+  "#;
+  let model = Series {
+    values: vec![1.0, 2.5, 3.7],
+    counts: Some(vec![4, 5]),
+    labels: vec!["a".to_string(), "b c".to_string()],
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Series);
+
+  let content = "<series><values /><counts /><labels /></series>";
+  let model = Series {
+    values: vec![],
+    counts: Some(vec![]),
+    labels: vec![],
+  };
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Series);
+
+  let model = Series {
+    values: vec![],
+    counts: None,
+    labels: vec![],
+  };
+
+  serialize_and_validate!(model, "<series><values /><labels /></series>");
+  deserialize_and_validate!("<series />", model, Series);
+}
@@ -290,8 +290,7 @@ fn struct_implicit_default_namespace() {
 
   let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata"><item>something</item></tt>"#;
   serialize_and_validate!(model, content);
-  // TODO
-  // deserialize_and_validate!(content, model, XmlStruct);
+  deserialize_and_validate!(content, model, XmlStruct);
 }
 
 #[test]
@@ -317,8 +316,7 @@ fn struct_explicit_default_namespace() {
 
   let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttm="http://www.w3.org/ns/ttml#metadata"><item>something</item></tt>"#;
   serialize_and_validate!(model, content);
-  // TODO
-  // deserialize_and_validate!(content, model, XmlStruct);
+  deserialize_and_validate!(content, model, XmlStruct);
 }
 
 #[test]
@@ -415,8 +413,7 @@ fn enum_multi_namespaces() {
     </root>
   "#;
   serialize_and_validate!(model, content);
-  // TODO
-  // deserialize_and_validate!(content, model, XmlStruct);
+  deserialize_and_validate!(content, model, XmlStruct);
 }
 
 #[test]
@@ -523,3 +520,581 @@ fn struct_default_namespace_no_prefix() {
   serialize_and_validate!(model, content);
   deserialize_and_validate!(content, model, Book);
 }
+
+#[test]
+fn struct_attribute_namespace_matching() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "image",
+    namespaces = {
+      "xlink" = "http://www.w3.org/1999/xlink"
+    }
+  )]
+  pub struct Image {
+    #[yaserde(attribute = true, rename = "href")]
+    local: Option<String>,
+    #[yaserde(attribute = true, prefix = "xlink", rename = "href")]
+    link: String,
+  }
+
+  let model = Image {
+    local: Some("local.png".to_string()),
+    link: "remote.png".to_string(),
+  };
+  let content = r#"<image xmlns:xlink="http://www.w3.org/1999/xlink" href="local.png" xlink:href="remote.png" />"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Image);
+
+  // Prefixes are resolved to their namespace, and foreign attributes are ignored
+  let content = r#"<image xmlns:l="http://www.w3.org/1999/xlink" xmlns:other="http://www.sample.com/ns/other" other:href="foreign.png" l:href="remote.png" />"#;
+  let model = Image {
+    local: None,
+    link: "remote.png".to_string(),
+  };
+  deserialize_and_validate!(content, model, Image);
+}
+
+#[test]
+fn struct_attribute_in_default_namespace() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "tt",
+    prefix = "ttml",
+    default_namespace = "ttml",
+    namespaces = {
+      "ttml" = "http://www.w3.org/ns/ttml"
+    }
+  )]
+  pub struct XmlStruct {
+    #[yaserde(attribute = true, prefix = "ttml")]
+    role: String,
+    #[yaserde(attribute = true, prefix = "xml")]
+    lang: String,
+    #[yaserde(prefix = "ttml")]
+    item: String,
+  }
+
+  let model = XmlStruct {
+    role: "caption".to_string(),
+    lang: "en".to_string(),
+    item: "something".to_string(),
+  };
+
+  let content = r#"<tt xmlns="http://www.w3.org/ns/ttml" xmlns:ttml="http://www.w3.org/ns/ttml" ttml:role="caption" xml:lang="en"><item>something</item></tt>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, XmlStruct);
+}
+
+mod term {
+  use std::io::Write;
+  use xml::name::Name;
+  use xml::writer::XmlEvent;
+
+  pub fn serialize<W: Write>(
+    value: &str,
+    writer: &mut yaserde::ser::Serializer<W>,
+  ) -> Result<(), String> {
+    let name = Name {
+      local_name: "term",
+      namespace: Some("http://purl.org/dc/terms/"),
+      prefix: None,
+    };
+    writer
+      .write(XmlEvent::start_element(name))
+      .map_err(|e| e.to_string())?;
+    writer
+      .write(XmlEvent::characters(value))
+      .map_err(|e| e.to_string())?;
+    writer
+      .write(XmlEvent::end_element())
+      .map_err(|e| e.to_string())
+  }
+}
+
+#[test]
+fn ser_preferred_prefixes() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "record")]
+  pub struct Record {
+    #[yaserde(prefix = "dc")]
+    title: String,
+    #[yaserde(attribute = true, prefix = "dc")]
+    language: String,
+  }
+
+  let model = Record {
+    title: "Report".to_string(),
+    language: "en".to_string(),
+  };
+
+  let mut namespaces = std::collections::BTreeMap::new();
+  namespaces.insert(
+    "dc".to_string(),
+    "http://purl.org/dc/elements/1.1/".to_string(),
+  );
+  let config = yaserde::ser::Config {
+    write_document_declaration: false,
+    namespaces,
+    ..Default::default()
+  };
+
+  let content = r#"<record xmlns:dc="http://purl.org/dc/elements/1.1/" dc:language="en"><dc:title>Report</dc:title></record>"#;
+  let data = yaserde::ser::to_string_with_config(&model, &config).unwrap();
+  assert_eq!(data, content);
+
+  // The written declarations bind the prefixes when reading back
+  let loaded: Record = yaserde::de::from_str(&data).unwrap();
+  assert_eq!(loaded, model);
+
+  // The same prefixes are given to the deserializer for a document using other ones
+  let de_config = yaserde::de::Config {
+    namespaces: config.namespaces.clone(),
+    ..Default::default()
+  };
+  let content = r#"<record xmlns:elements="http://purl.org/dc/elements/1.1/" elements:language="en"><elements:title>Report</elements:title></record>"#;
+  let loaded: Record = yaserde::de::from_str_with_config(content, &de_config).unwrap();
+  assert_eq!(loaded, model);
+}
+
+#[test]
+fn ser_hoisted_namespaces() {
+  init();
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "entry")]
+  pub struct Entry {
+    #[yaserde(prefix = "dc")]
+    title: String,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "feed")]
+  pub struct Feed {
+    #[yaserde(rename = "entry")]
+    entries: Vec<Entry>,
+  }
+
+  let model = Feed {
+    entries: vec![
+      Entry {
+        title: "First".to_string(),
+      },
+      Entry {
+        title: "Second".to_string(),
+      },
+    ],
+  };
+
+  let mut namespaces = std::collections::BTreeMap::new();
+  namespaces.insert(
+    "dc".to_string(),
+    "http://purl.org/dc/elements/1.1/".to_string(),
+  );
+  let config = yaserde::ser::Config {
+    write_document_declaration: false,
+    namespaces,
+    ..Default::default()
+  };
+
+  let content = r#"<feed><entry><dc:title xmlns:dc="http://purl.org/dc/elements/1.1/">First</dc:title></entry><entry><dc:title xmlns:dc="http://purl.org/dc/elements/1.1/">Second</dc:title></entry></feed>"#;
+  let data = yaserde::ser::to_string_with_config(&model, &config).unwrap();
+  assert_eq!(data, content);
+
+  let config = yaserde::ser::Config {
+    hoist_namespaces: true,
+    ..config
+  };
+
+  let content = r#"<feed xmlns:dc="http://purl.org/dc/elements/1.1/"><entry><dc:title>First</dc:title></entry><entry><dc:title>Second</dc:title></entry></feed>"#;
+  let data = yaserde::ser::to_string_with_config(&model, &config).unwrap();
+  assert_eq!(data, content);
+}
+
+#[test]
+fn ser_namespace_uri_without_prefix() {
+  init();
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(
+    rename = "record",
+    namespaces = {
+      "terms" = "http://purl.org/dc/terms/"
+    }
+  )]
+  pub struct Record {
+    #[yaserde(serialize_with = "term::serialize")]
+    known: String,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "record")]
+  pub struct Other {
+    #[yaserde(serialize_with = "term::serialize")]
+    unknown: String,
+  }
+
+  let model = Record {
+    known: "value".to_string(),
+  };
+  let content = r#"<record xmlns:terms="http://purl.org/dc/terms/"><known><terms:term>value</terms:term></known></record>"#;
+  serialize_and_validate!(model, content);
+
+  let model = Other {
+    unknown: "value".to_string(),
+  };
+  let content = r#"<record><unknown><ns1:term xmlns:ns1="http://purl.org/dc/terms/">value</ns1:term></unknown></record>"#;
+  serialize_and_validate!(model, content);
+}
+
+#[test]
+fn ser_redundant_declarations() {
+  init();
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(
+    rename = "item",
+    prefix = "dc",
+    namespaces = {
+      "dc" = "http://purl.org/dc/elements/1.1/"
+    }
+  )]
+  pub struct Item {
+    #[yaserde(prefix = "dc")]
+    title: String,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(
+    rename = "list",
+    prefix = "dc",
+    namespaces = {
+      "dc" = "http://purl.org/dc/elements/1.1/"
+    }
+  )]
+  pub struct List {
+    #[yaserde(prefix = "dc")]
+    item: Item,
+  }
+
+  let model = List {
+    item: Item {
+      title: "Report".to_string(),
+    },
+  };
+  let content = r#"<dc:list xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:item><dc:title>Report</dc:title></dc:item></dc:list>"#;
+  serialize_and_validate!(model, content);
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "part")]
+  pub struct Part {
+    #[yaserde(attribute = true)]
+    xmlns: String,
+  }
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "assembly")]
+  pub struct Assembly {
+    #[yaserde(attribute = true)]
+    xmlns: String,
+    part: Part,
+  }
+
+  let model = Assembly {
+    xmlns: "urn:parts".to_string(),
+    part: Part {
+      xmlns: "urn:parts".to_string(),
+    },
+  };
+  let content = r#"<assembly xmlns="urn:parts"><part /></assembly>"#;
+  serialize_and_validate!(model, content);
+}
+
+yaserde::namespaces! {
+  mod shared {
+    soap = "http://schemas.xmlsoap.org/soap/envelope/",
+    xs = "http://www.w3.org/2001/XMLSchema",
+  }
+}
+
+#[test]
+fn struct_namespaces_table() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(prefix = "xs", namespaces = shared)]
+  pub enum Kind {
+    #[yaserde(rename = "string")]
+    Text,
+    #[yaserde(rename = "int")]
+    Integer,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "Body", prefix = "soap", namespaces = shared)]
+  pub struct Body {
+    #[yaserde(attribute = true, prefix = "xs")]
+    kind: String,
+    #[yaserde(prefix = "xs")]
+    element: Kind,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "Envelope", prefix = "soap", namespaces = shared)]
+  pub struct Envelope {
+    #[yaserde(prefix = "soap", rename = "Body")]
+    body: Body,
+  }
+
+  let model = Envelope {
+    body: Body {
+      kind: "request".to_string(),
+      element: Kind::Integer,
+    },
+  };
+
+  let content = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/" xmlns:xs="http://www.w3.org/2001/XMLSchema"><soap:Body xs:kind="request"><xs:element>int</xs:element></soap:Body></soap:Envelope>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Envelope);
+
+  let content = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/" xmlns:xs="http://www.w3.org/2001/XMLSchema"><soap:Body xmlns:xs="urn:other" xs:kind="request"><xs:element>int</xs:element></soap:Body></soap:Envelope>"#;
+  let loaded: Result<Envelope, String> = yaserde::de::from_str(content);
+  assert!(loaded.is_err());
+}
+
+#[test]
+fn struct_namespaces_table_and_map() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "Envelope",
+    prefix = "soap",
+    default_namespace = "soap",
+    namespaces = shared,
+    namespaces = {
+      "ext" = "urn:extension"
+    }
+  )]
+  pub struct Envelope {
+    #[yaserde(prefix = "ext")]
+    header: String,
+  }
+
+  let model = Envelope {
+    header: "value".to_string(),
+  };
+
+  let content = r#"<Envelope xmlns="http://schemas.xmlsoap.org/soap/envelope/" xmlns:ext="urn:extension" xmlns:xs="http://www.w3.org/2001/XMLSchema"><ext:header>value</ext:header></Envelope>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Envelope);
+}
+
+#[test]
+fn de_any_prefix_convention() {
+  init();
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "order",
+    prefix = "a",
+    namespaces = {
+      "a" = "urn:alpha",
+      "b" = "urn:beta"
+    }
+  )]
+  pub struct Order {
+    #[yaserde(attribute = true, prefix = "b")]
+    id: String,
+    #[yaserde(attribute = true)]
+    version: String,
+    #[yaserde(prefix = "a")]
+    item: Item,
+    #[yaserde(prefix = "b")]
+    status: Status,
+    #[yaserde(prefix = "a")]
+    payment: Payment,
+    #[yaserde(flatten = true)]
+    shipping: Shipping,
+  }
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    prefix = "b",
+    namespaces = {
+      "a" = "urn:alpha",
+      "b" = "urn:beta"
+    }
+  )]
+  pub struct Item {
+    #[yaserde(attribute = true, prefix = "a")]
+    quantity: u32,
+    #[yaserde(prefix = "b")]
+    name: String,
+  }
+
+  #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    prefix = "b",
+    namespaces = {
+      "b" = "urn:beta"
+    }
+  )]
+  pub enum Status {
+    #[default]
+    #[yaserde(prefix = "b", rename = "open")]
+    Open,
+    #[yaserde(prefix = "b", rename = "closed")]
+    Closed,
+  }
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    prefix = "a",
+    namespaces = {
+      "a" = "urn:alpha",
+      "b" = "urn:beta"
+    }
+  )]
+  pub enum Payment {
+    #[yaserde(prefix = "a", rename = "card")]
+    Card(String),
+    #[yaserde(prefix = "b", rename = "card")]
+    Voucher(String),
+  }
+
+  #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    flatten = true,
+    namespaces = {
+      "a" = "urn:alpha",
+      "b" = "urn:beta"
+    }
+  )]
+  pub struct Shipping {
+    #[yaserde(prefix = "b")]
+    carrier: String,
+    #[yaserde(prefix = "a")]
+    tracking: String,
+  }
+
+  let model = Order {
+    id: "42".to_string(),
+    version: "2".to_string(),
+    item: Item {
+      quantity: 3,
+      name: "pen".to_string(),
+    },
+    status: Status::Closed,
+    payment: Payment::Voucher("gift".to_string()),
+    shipping: Shipping {
+      carrier: "post".to_string(),
+      tracking: "T-1".to_string(),
+    },
+  };
+
+  let declared_prefixes = r#"
+    <a:order xmlns:a="urn:alpha" xmlns:b="urn:beta" b:id="42" version="2">
+      <a:item a:quantity="3"><b:name>pen</b:name></a:item>
+      <b:status>b:closed</b:status>
+      <a:payment><b:card>gift</b:card></a:payment>
+      <b:carrier>post</b:carrier>
+      <a:tracking>T-1</a:tracking>
+    </a:order>
+  "#;
+  let other_prefixes = r#"
+    <ns0:order xmlns:ns0="urn:alpha" xmlns:ns1="urn:beta" ns1:id="42" version="2">
+      <ns0:item ns0:quantity="3"><ns1:name>pen</ns1:name></ns0:item>
+      <ns1:status>ns1:closed</ns1:status>
+      <ns0:payment><ns1:card>gift</ns1:card></ns0:payment>
+      <ns1:carrier>post</ns1:carrier>
+      <ns0:tracking>T-1</ns0:tracking>
+    </ns0:order>
+  "#;
+  let default_namespace = r#"
+    <order xmlns="urn:alpha" xmlns:p="urn:alpha" xmlns:s="urn:beta" s:id="42" version="2">
+      <item p:quantity="3"><name xmlns="urn:beta">pen</name></item>
+      <s:status>s:closed</s:status>
+      <payment><card xmlns="urn:beta">gift</card></payment>
+      <s:carrier>post</s:carrier>
+      <tracking>T-1</tracking>
+    </order>
+  "#;
+
+  for content in &[declared_prefixes, other_prefixes, default_namespace] {
+    deserialize_and_validate!(content, model.clone(), Order);
+  }
+
+  // The prefix of a value is resolved in the document, not compared to the declared one
+  let content = r#"<b:status xmlns:a="urn:alpha" xmlns:b="urn:beta">a:closed</b:status>"#;
+  let loaded: Result<Status, String> = yaserde::de::from_str(content);
+  assert_eq!(
+    loaded,
+    Err(
+      "Unknown value \"a:closed\" for enum Status, expected one of: b:open, b:closed".to_string()
+    )
+  );
+
+  let content =
+    r#"<a:payment xmlns:a="urn:alpha" xmlns:b="urn:beta"><a:card>1234</a:card></a:payment>"#;
+  deserialize_and_validate!(content, Payment::Card("1234".to_string()), Payment);
+}
+
+#[test]
+fn de_undeclared_prefixes() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize)]
+  #[yaserde(rename = "record")]
+  pub struct Record {
+    #[yaserde(prefix = "dc")]
+    title: String,
+    #[yaserde(prefix = "dc", alias = "dc:name")]
+    creator: String,
+    #[yaserde(attribute = true, prefix = "dc")]
+    language: String,
+    #[yaserde(rename = "subject", wrapper = "dc:subjects")]
+    subjects: Vec<String>,
+  }
+
+  let model = Record {
+    title: "Report".to_string(),
+    creator: "Ada".to_string(),
+    language: "en".to_string(),
+    subjects: vec!["xml".to_string()],
+  };
+
+  // The prefixes are bound by the document
+  let content = r#"<record xmlns:dc="http://purl.org/dc/elements/1.1/" dc:language="en"><dc:title>Report</dc:title><dc:name>Ada</dc:name><dc:subjects><subject>xml</subject></dc:subjects></record>"#;
+  let loaded: Record = yaserde::de::from_str(content).unwrap();
+  assert_eq!(loaded, model);
+
+  // The config binds the prefixes before the document
+  let content = r#"<record xmlns:terms="http://purl.org/dc/elements/1.1/" xmlns:dc="urn:other" terms:language="en"><terms:title>Report</terms:title><dc:title>Other</dc:title><terms:name>Ada</terms:name><terms:subjects><subject>xml</subject></terms:subjects></record>"#;
+  let loaded: Record = yaserde::de::from_str_with_config(
+    content,
+    &yaserde::de::Config {
+      namespaces: [(
+        "dc".to_string(),
+        "http://purl.org/dc/elements/1.1/".to_string(),
+      )]
+      .into(),
+      ..Default::default()
+    },
+  )
+  .unwrap();
+  assert_eq!(loaded, model);
+
+  // An element in another namespace is not the field
+  let content = r#"<record xmlns:dc="http://purl.org/dc/elements/1.1/" dc:language="en"><title>Report</title></record>"#;
+  let loaded: Result<Record, String> = yaserde::de::from_str(content);
+  assert_eq!(
+    loaded,
+    Err("title is a required field of Record".to_string())
+  );
+}
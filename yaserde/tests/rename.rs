@@ -0,0 +1,217 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn rename_all_struct() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "purchaseOrder", rename_all = "camelCase")]
+  pub struct PurchaseOrder {
+    #[yaserde(attribute = true)]
+    order_date: String,
+    #[yaserde(attribute = true, rename = "ID")]
+    order_id: u32,
+    ship_to: String,
+    #[yaserde(rename = "bill-to")]
+    bill_to: String,
+    r#type: String,
+    line_items: Vec<u32>,
+  }
+
+  let model = PurchaseOrder {
+    order_date: "1999-10-20".to_string(),
+    order_id: 42,
+    ship_to: "Alice".to_string(),
+    bill_to: "Bob".to_string(),
+    r#type: "express".to_string(),
+    line_items: vec![1, 2],
+  };
+  let content = r#"<purchaseOrder orderDate="1999-10-20" ID="42"><shipTo>Alice</shipTo><bill-to>Bob</bill-to><type>express</type><lineItems>1</lineItems><lineItems>2</lineItems></purchaseOrder>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, PurchaseOrder);
+}
+
+#[test]
+fn rename_all_attributes() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "Config",
+    rename_all = "PascalCase",
+    rename_all_attributes = "kebab-case"
+  )]
+  pub struct Config {
+    #[yaserde(attribute = true)]
+    max_connections: u32,
+    #[yaserde(attribute = true)]
+    log_level: Option<String>,
+    server_name: String,
+    #[yaserde(rename = "port")]
+    listen_port: u16,
+  }
+
+  let model = Config {
+    max_connections: 10,
+    log_level: Some("debug".to_string()),
+    server_name: "localhost".to_string(),
+    listen_port: 8080,
+  };
+  let content = r#"<Config max-connections="10" log-level="debug"><ServerName>localhost</ServerName><port>8080</port></Config>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Config);
+}
+
+#[test]
+fn rename_all_enum() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename_all = "SCREAMING_SNAKE_CASE")]
+  pub enum Status {
+    InProgress,
+    #[yaserde(rename = "done")]
+    Done,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename_all = "kebab-case")]
+  pub enum Shape {
+    Circle(f64),
+    RoundedSquare(f64),
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "task", rename_all = "lowercase")]
+  pub struct Task {
+    #[yaserde(attribute = true)]
+    status: Status,
+    #[yaserde(attribute = true)]
+    previous_status: Status,
+    shape: Shape,
+  }
+
+  let model = Task {
+    status: Status::InProgress,
+    previous_status: Status::Done,
+    shape: Shape::RoundedSquare(2.5),
+  };
+  let content = r#"<task status="IN_PROGRESS" previous_status="done"><shape><rounded-square>2.5</rounded-square></shape></task>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Task);
+}
+
+#[test]
+fn alias_struct_fields() {
+  init();
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(
+    rename = "device",
+    prefix = "v2",
+    namespaces = {
+      "v1" = "urn:device:v1",
+      "v2" = "urn:device:v2"
+    }
+  )]
+  pub struct Device {
+    #[yaserde(attribute = true, alias = "ID", alias = "Id")]
+    id: u32,
+    #[yaserde(
+      prefix = "v2",
+      rename = "baseAddress",
+      alias = "baseaddress",
+      alias = "v1:base"
+    )]
+    base_address: String,
+    #[yaserde(prefix = "v2", alias = "labels")]
+    label: Vec<String>,
+  }
+
+  let model = Device {
+    id: 7,
+    base_address: "0x4000".to_string(),
+    label: vec!["uart".to_string(), "serial".to_string()],
+  };
+  let content = r#"
+    <v2:device xmlns:v1="urn:device:v1" xmlns:v2="urn:device:v2" id="7">
+      <v2:baseAddress>0x4000</v2:baseAddress>
+      <v2:label>uart</v2:label>
+      <v2:label>serial</v2:label>
+    </v2:device>
+  "#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model.clone(), Device);
+
+  let content = r#"
+    <v2:device xmlns:v1="urn:device:v1" xmlns:v2="urn:device:v2" ID="7">
+      <v2:baseaddress>0x4000</v2:baseaddress>
+      <v2:labels>uart</v2:labels>
+      <v2:label>serial</v2:label>
+    </v2:device>
+  "#;
+  deserialize_and_validate!(content, model.clone(), Device);
+
+  let content = r#"
+    <v2:device xmlns:v1="urn:device:v1" xmlns:v2="urn:device:v2" Id="7">
+      <v1:base>0x4000</v1:base>
+      <v2:label>uart</v2:label>
+      <v2:label>serial</v2:label>
+    </v2:device>
+  "#;
+  deserialize_and_validate!(content, model, Device);
+}
+
+#[test]
+fn alias_enum_variants() {
+  init();
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  pub enum Access {
+    #[yaserde(rename = "read-only", alias = "readOnly", alias = "ro")]
+    ReadOnly,
+    #[yaserde(rename = "read-write", alias = "readWrite")]
+    ReadWrite,
+  }
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  pub enum Size {
+    #[yaserde(alias = "bits")]
+    Width(u32),
+    Bytes(u32),
+  }
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "register")]
+  pub struct Register {
+    #[yaserde(attribute = true)]
+    access: Access,
+    size: Size,
+  }
+
+  let model = Register {
+    access: Access::ReadOnly,
+    size: Size::Width(32),
+  };
+  let content = r#"<register access="read-only"><size><Width>32</Width></size></register>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model.clone(), Register);
+
+  let content = r#"<register access="ro"><size><bits>32</bits></size></register>"#;
+  deserialize_and_validate!(content, model.clone(), Register);
+
+  let content = r#"<register access="readOnly"><size><Width>32</Width></size></register>"#;
+  deserialize_and_validate!(content, model, Register);
+}
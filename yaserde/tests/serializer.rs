@@ -415,3 +415,52 @@ fn ser_custom() {
   let content = "<Date><Year>2020</Year><Month>1</Month><DoubleDay>10</DoubleDay></Date>";
   serialize_and_validate!(model, content);
 }
+
+#[test]
+fn ser_document_declaration() {
+  #[derive(YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    item: String,
+  }
+
+  let model = XmlStruct {
+    item: "something".to_string(),
+  };
+
+  // the encoding is written in lowercase, whatever the xml-rs version
+  assert_eq!(
+    yaserde::ser::to_string(&model),
+    Ok(r#"<?xml version="1.0" encoding="utf-8"?><base><item>something</item></base>"#.to_string())
+  );
+
+  let config = yaserde::ser::Config {
+    write_document_declaration: false,
+    ..Default::default()
+  };
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config),
+    Ok("<base><item>something</item></base>".to_string())
+  );
+
+  let config = yaserde::ser::Config {
+    perform_indent: true,
+    ..Default::default()
+  };
+  assert_eq!(
+    yaserde::ser::to_string_with_config(&model, &config),
+    Ok(
+      r#"<?xml version="1.0" encoding="utf-8"?>
+<base>
+  <item>something</item>
+</base>"#
+        .to_string()
+    )
+  );
+
+  // a content alone is not a document
+  assert_eq!(
+    yaserde::ser::to_string_content(&model),
+    Ok("<item>something</item>".to_string())
+  );
+}
@@ -92,3 +92,78 @@ fn skip_serializing_for_vec() {
   let content = "<base />";
   serialize_and_validate!(model, content);
 }
+
+#[test]
+fn skip_deserializing() {
+  init();
+
+  fn default_label() -> String {
+    "unnamed".to_string()
+  }
+
+  #[derive(YaDeserialize, YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(attribute = true)]
+    id: u32,
+    #[yaserde(skip_deserializing = true, default = "default_label")]
+    label: String,
+    #[yaserde(attribute = true, skip_deserializing = true)]
+    index: u32,
+    value: i32,
+  }
+
+  let content = r#"<base id="7" index="3"><label>ignored</label><value>12</value></base>"#;
+  let model = XmlStruct {
+    id: 7,
+    label: "unnamed".to_string(),
+    index: 0,
+    value: 12,
+  };
+  deserialize_and_validate!(content, model, XmlStruct);
+
+  let model = XmlStruct {
+    id: 7,
+    label: "computed".to_string(),
+    index: 3,
+    value: 12,
+  };
+  let content = r#"<base id="7" index="3"><label>computed</label><value>12</value></base>"#;
+  serialize_and_validate!(model, content);
+}
+
+#[test]
+fn skip() {
+  init();
+
+  #[derive(YaDeserialize, YaSerialize, PartialEq, Debug)]
+  #[yaserde(rename = "base")]
+  pub struct XmlStruct {
+    #[yaserde(attribute = true)]
+    name: String,
+    #[yaserde(attribute = true, skip = true)]
+    revision: u32,
+    #[yaserde(skip = true)]
+    cache: std::collections::HashMap<String, usize>,
+    #[yaserde(text = true)]
+    content: String,
+  }
+
+  let model = XmlStruct {
+    name: "register".to_string(),
+    revision: 4,
+    cache: vec![("register".to_string(), 1)].into_iter().collect(),
+    content: "CTRL".to_string(),
+  };
+  let content = r#"<base name="register">CTRL</base>"#;
+  serialize_and_validate!(model, content);
+
+  let model = XmlStruct {
+    name: "register".to_string(),
+    revision: 0,
+    cache: Default::default(),
+    content: "CTRL".to_string(),
+  };
+  let content = r#"<base name="register" revision="4">CTRL</base>"#;
+  deserialize_and_validate!(content, model, XmlStruct);
+}
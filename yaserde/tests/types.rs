@@ -47,3 +47,269 @@ fn ser_type() {
   test_for_attribute_type!(f32, -12.5_f32, Some("-12.5"));
   test_for_attribute_type!(f64, -12.5_f64, Some("-12.5"));
 }
+
+#[test]
+fn ser_extended_primitive_types() {
+  init();
+
+  use std::num::{NonZeroI64, NonZeroU8, NonZeroUsize};
+
+  test_for_type!(char, 'x', Some("x"));
+  test_for_type!(usize, 12_usize, Some("12"));
+  test_for_type!(isize, -12_isize, Some("-12"));
+  test_for_type!(
+    u128,
+    u128::MAX,
+    Some("340282366920938463463374607431768211455")
+  );
+  test_for_type!(
+    i128,
+    i128::MIN,
+    Some("-170141183460469231731687303715884105728")
+  );
+  test_for_type!(NonZeroU8, NonZeroU8::new(12).unwrap(), Some("12"));
+  test_for_type!(NonZeroI64, NonZeroI64::new(-12).unwrap(), Some("-12"));
+  test_for_type!(
+    std::num::NonZeroU32,
+    std::num::NonZeroU32::new(12).unwrap(),
+    Some("12")
+  );
+  test_for_type!(Option::<usize>, Some(12_usize), Some("12"));
+  test_for_type!(Option::<char>, None, None);
+  test_for_type!(Vec::<usize>, vec![1, 2], Some("1</item><item>2"));
+  test_for_type!(
+    Vec::<NonZeroUsize>,
+    vec![NonZeroUsize::new(3).unwrap()],
+    Some("3")
+  );
+
+  test_for_attribute_type!(char, 'x', Some("x"));
+  test_for_attribute_type!(usize, 12_usize, Some("12"));
+  test_for_attribute_type!(isize, -12_isize, Some("-12"));
+  test_for_attribute_type!(u128, 12_u128, Some("12"));
+  test_for_attribute_type!(i128, -12_i128, Some("-12"));
+  test_for_attribute_type!(NonZeroU8, NonZeroU8::new(12).unwrap(), Some("12"));
+  test_for_attribute_type!(Option::<usize>, Some(12_usize), Some("12"));
+  test_for_attribute_type!(Option::<NonZeroI64>, None, None);
+}
+
+#[test]
+fn de_extended_primitive_types() {
+  init();
+
+  use std::num::{NonZeroI128, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU64};
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "data")]
+  pub struct Data {
+    letter: char,
+    count: usize,
+    offset: isize,
+    big: u128,
+    small: i128,
+    level: NonZeroI8,
+    port: NonZeroU16,
+    id: NonZeroU64,
+    delta: NonZeroIsize,
+    letters: Vec<char>,
+    sizes: Vec<u128>,
+    steps: Vec<NonZeroI128>,
+  }
+
+  let content = r#"
+    <data>
+      <letter>é</letter>
+      <count>18446744073709551615</count>
+      <offset>-3</offset>
+      <big>340282366920938463463374607431768211455</big>
+      <small>-170141183460469231731687303715884105728</small>
+      <level>-1</level>
+      <port>8080</port>
+      <id>42</id>
+      <delta>-7</delta>
+      <letters>a</letters>
+      <letters>b</letters>
+      <sizes>1</sizes>
+      <sizes>2</sizes>
+      <steps>-1</steps>
+      <steps>170141183460469231731687303715884105727</steps>
+    </data>
+  "#;
+  let model = Data {
+    letter: 'é',
+    count: usize::MAX,
+    offset: -3,
+    big: u128::MAX,
+    small: i128::MIN,
+    level: NonZeroI8::new(-1).unwrap(),
+    port: NonZeroU16::new(8080).unwrap(),
+    id: NonZeroU64::new(42).unwrap(),
+    delta: NonZeroIsize::new(-7).unwrap(),
+    letters: vec!['a', 'b'],
+    sizes: vec![1, 2],
+    steps: vec![
+      NonZeroI128::new(-1).unwrap(),
+      NonZeroI128::new(i128::MAX).unwrap(),
+    ],
+  };
+
+  deserialize_and_validate!(content, model, Data);
+
+  let result: Result<Data, String> =
+    yaserde::de::from_str(&content.replace("<level>-1</level>", "<level>0</level>"));
+  assert!(result.is_err());
+  let result: Result<Data, String> =
+    yaserde::de::from_str(&content.replace("<letter>é</letter>", "<letter>ab</letter>"));
+  assert!(result.is_err());
+}
+
+#[test]
+fn extended_primitive_types_as_text() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "data")]
+  pub struct Data {
+    #[yaserde(attribute = true)]
+    index: std::num::NonZeroUsize,
+    #[yaserde(text = true)]
+    value: usize,
+  }
+
+  let model = Data {
+    index: std::num::NonZeroUsize::new(1).unwrap(),
+    value: 42,
+  };
+  let content = r#"<data index="1">42</data>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Data);
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "data")]
+  pub struct OptionalData {
+    #[yaserde(text = true)]
+    value: Option<char>,
+  }
+
+  let model = OptionalData { value: Some('c') };
+  let content = "<data>c</data>";
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, OptionalData);
+
+  let model = OptionalData { value: None };
+  deserialize_and_validate!("<data />", model, OptionalData);
+}
+
+#[test]
+fn std_net_and_path_types() {
+  init();
+
+  use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+  use std::path::PathBuf;
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "server")]
+  pub struct Server {
+    #[yaserde(attribute = true)]
+    address: IpAddr,
+    #[yaserde(attribute = true)]
+    gateway: Option<Ipv4Addr>,
+    listen: Vec<SocketAddr>,
+    #[yaserde(attribute = true)]
+    allowed: Vec<Ipv6Addr>,
+    root: PathBuf,
+  }
+
+  let model = Server {
+    address: IpAddr::V6(Ipv6Addr::LOCALHOST),
+    gateway: Some(Ipv4Addr::new(192, 168, 0, 1)),
+    listen: vec!["0.0.0.0:80".parse().unwrap(), "[::1]:8443".parse().unwrap()],
+    allowed: vec![Ipv6Addr::UNSPECIFIED, "fe80::1".parse().unwrap()],
+    root: PathBuf::from("/var/www/html"),
+  };
+  let content = r#"<server address="::1" gateway="192.168.0.1" allowed=":: fe80::1"><listen>0.0.0.0:80</listen><listen>[::1]:8443</listen><root>/var/www/html</root></server>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Server);
+
+  let result: Result<Server, String> =
+    yaserde::de::from_str(r#"<server address="localhost"><root>/</root></server>"#);
+  assert_eq!(result, Err("invalid IP address syntax".to_string()));
+}
+
+#[cfg(unix)]
+#[test]
+fn non_unicode_path() {
+  init();
+
+  use std::ffi::OsStr;
+  use std::os::unix::ffi::OsStrExt;
+  use std::path::PathBuf;
+
+  #[derive(Debug, PartialEq, YaSerialize)]
+  #[yaserde(rename = "server")]
+  pub struct Server {
+    root: PathBuf,
+  }
+
+  let model = Server {
+    root: PathBuf::from(OsStr::from_bytes(b"/var/\xFF")),
+  };
+  let result = yaserde::ser::to_string(&model);
+  assert_eq!(
+    result,
+    Err(r#"Path "/var/\xFF" is not valid Unicode"#.to_string())
+  );
+}
+
+#[cfg(all(feature = "rust_decimal", feature = "url", feature = "uuid"))]
+#[test]
+fn uuid_url_and_decimal_types() {
+  init();
+
+  use rust_decimal::Decimal;
+  use url::Url;
+  use uuid::Uuid;
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "invoice")]
+  pub struct Invoice {
+    #[yaserde(attribute = true)]
+    id: Uuid,
+    #[yaserde(attribute = true)]
+    parent: Option<Uuid>,
+    total: Decimal,
+    link: Option<Url>,
+  }
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "amount")]
+  pub struct Amount {
+    #[yaserde(text = true)]
+    value: Decimal,
+  }
+
+  let model = Invoice {
+    id: Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+    parent: None,
+    total: Decimal::new(12_050, 2),
+    link: Some(Url::parse("https://example.com/invoices?id=1&lang=en").unwrap()),
+  };
+  let content = r#"<invoice id="67e55044-10b1-426f-9247-bb680e5fe0c8"><total>120.50</total><link>https://example.com/invoices?id=1&amp;lang=en</link></invoice>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Invoice);
+
+  let model = Amount {
+    value: Decimal::new(-5, 1),
+  };
+
+  serialize_and_validate!(model, "<amount>-0.5</amount>");
+  deserialize_and_validate!("<amount>-0.5</amount>", model, Amount);
+
+  let result: Result<Invoice, String> =
+    yaserde::de::from_str(r#"<invoice id="not-a-uuid"><total>1</total></invoice>"#);
+  assert!(result.unwrap_err().starts_with("invalid character"));
+}
@@ -0,0 +1,179 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use std::io::{Read, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use xml::reader::XmlEvent;
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+mod epoch {
+  use super::*;
+
+  pub fn serialize<W: Write>(
+    value: &SystemTime,
+    writer: &mut yaserde::ser::Serializer<W>,
+  ) -> Result<(), String> {
+    let seconds = value
+      .duration_since(UNIX_EPOCH)
+      .map_err(|e| e.to_string())?
+      .as_secs()
+      .to_string();
+
+    writer
+      .write(xml::writer::XmlEvent::characters(&seconds))
+      .map_err(|e| e.to_string())
+  }
+
+  pub fn deserialize<R: Read>(
+    reader: &mut yaserde::de::Deserializer<R>,
+  ) -> Result<SystemTime, String> {
+    if let XmlEvent::Characters(text) = reader.peek()?.to_owned() {
+      reader.next_event()?;
+      let seconds = text
+        .parse()
+        .map_err(|e: std::num::ParseIntError| e.to_string())?;
+      Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+    } else {
+      Err("Missing timestamp".to_string())
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Access {
+  Read,
+  Write,
+}
+
+mod comma_separated {
+  use super::Access;
+
+  pub fn serialize(values: &[Access]) -> Result<String, String> {
+    let names: Vec<&str> = values
+      .iter()
+      .map(|value| match value {
+        Access::Read => "r",
+        Access::Write => "w",
+      })
+      .collect();
+    Ok(names.join(","))
+  }
+
+  pub fn deserialize(value: &str) -> Result<Vec<Access>, String> {
+    value
+      .split(',')
+      .map(|name| match name {
+        "r" => Ok(Access::Read),
+        "w" => Ok(Access::Write),
+        _ => Err(format!("Unknown access {:?}", name)),
+      })
+      .collect()
+  }
+}
+
+fn to_hex(value: &u32) -> Result<String, String> {
+  Ok(format!("0x{:X}", value))
+}
+
+fn from_hex(value: &str) -> Result<u32, String> {
+  let digits = value.trim_start_matches("0x");
+  u32::from_str_radix(digits, 16).map_err(|e| e.to_string())
+}
+
+#[test]
+fn with_module_for_elements() {
+  init();
+
+  #[derive(Clone, Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "log")]
+  pub struct Log {
+    #[yaserde(with = "epoch")]
+    created: SystemTime,
+    #[yaserde(with = "epoch")]
+    updated: Option<SystemTime>,
+    message: String,
+  }
+
+  let model = Log {
+    created: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+    updated: None,
+    message: "started".to_string(),
+  };
+  let content = "<log><created>1700000000</created><message>started</message></log>";
+  serialize_and_validate!(model.clone(), content);
+  deserialize_and_validate!(content, model, Log);
+
+  let model = Log {
+    created: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+    updated: Some(UNIX_EPOCH + Duration::from_secs(1_700_000_060)),
+    message: "started".to_string(),
+  };
+  let content = "<log><created>1700000000</created><updated>1700000060</updated><message>started</message></log>";
+  serialize_and_validate!(model.clone(), content);
+  deserialize_and_validate!(content, model, Log);
+}
+
+#[test]
+fn with_module_for_attributes() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "register")]
+  pub struct Register {
+    #[yaserde(attribute = true, with = "comma_separated")]
+    access: Vec<Access>,
+    #[yaserde(attribute = true, with = "comma_separated")]
+    reset: Option<Vec<Access>>,
+  }
+
+  let model = Register {
+    access: vec![Access::Read, Access::Write],
+    reset: None,
+  };
+  let content = r#"<register access="r,w" />"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Register);
+
+  let model = Register {
+    access: vec![Access::Read],
+    reset: Some(vec![Access::Write]),
+  };
+  let content = r#"<register access="r" reset="w" />"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Register);
+
+  let content = r#"<register access="x" />"#;
+  let loaded: Result<Register, String> = yaserde::de::from_str(content);
+  assert_eq!(loaded, Err("Unknown access \"x\"".to_string()));
+}
+
+#[test]
+fn serialize_with_and_deserialize_with() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "mask")]
+  pub struct Mask {
+    #[yaserde(
+      attribute = true,
+      serialize_with = "to_hex",
+      deserialize_with = "from_hex"
+    )]
+    width: u32,
+    #[yaserde(text = true, serialize_with = "to_hex", deserialize_with = "from_hex")]
+    value: u32,
+  }
+
+  let model = Mask {
+    width: 32,
+    value: 0xff00,
+  };
+  let content = r#"<mask width="0x20">0xFF00</mask>"#;
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(r#"<mask width="0x20">0xff00</mask>"#, model, Mask);
+}
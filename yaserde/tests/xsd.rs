@@ -0,0 +1,165 @@
+#[macro_use]
+extern crate yaserde;
+#[macro_use]
+extern crate yaserde_derive;
+
+use std::convert::TryFrom;
+use yaserde::xsd::{Duration, GMonthDay, GYear, GYearMonth};
+
+fn init() {
+  let _ = env_logger::builder().is_test(true).try_init();
+}
+
+#[test]
+fn xsd_partial_dates() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "release")]
+  pub struct Release {
+    #[yaserde(attribute = true)]
+    year: GYear,
+    #[yaserde(attribute = true)]
+    anniversary: Option<GMonthDay>,
+    month: GYearMonth,
+    founded: Option<GYear>,
+  }
+
+  let model = Release {
+    year: GYear::new(2024),
+    anniversary: Some(GMonthDay::new(2, 29).unwrap().with_timezone(60).unwrap()),
+    month: GYearMonth::new(2024, 3).unwrap().with_timezone(0).unwrap(),
+    founded: Some(GYear::new(-44).with_timezone(-330).unwrap()),
+  };
+  let content = r#"<release year="2024" anniversary="--02-29+01:00"><month>2024-03Z</month><founded>-0044-05:30</founded></release>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Release);
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "release")]
+  pub struct Text {
+    #[yaserde(text = true)]
+    value: GYearMonth,
+  }
+
+  let model = Text {
+    value: GYearMonth::new(12024, 12).unwrap(),
+  };
+
+  serialize_and_validate!(model, "<release>12024-12</release>");
+  deserialize_and_validate!("<release>12024-12</release>", model, Text);
+
+  for invalid in ["24", "02024", "2024-13", "2024-1", "2024+15:00", "２０２４"] {
+    assert!(invalid.parse::<GYearMonth>().is_err(), "{}", invalid);
+  }
+  for invalid in ["--02-30", "--13-01", "02-01", "--2-01"] {
+    assert!(invalid.parse::<GMonthDay>().is_err(), "{}", invalid);
+  }
+  assert_eq!(
+    "2024-5".parse::<GYear>(),
+    Err("Invalid gYear \"2024-5\"".to_string())
+  );
+  // timezones are checked as for the dates and times of the chrono and time features
+  assert_eq!(
+    "2024+14:30".parse::<GYear>(),
+    Err("Invalid timezone in \"2024+14:30\"".to_string())
+  );
+
+  assert!(GYear::new(2023) < GYear::new(2024));
+  assert!(GYearMonth::new(2023, 12).unwrap() < GYearMonth::new(2024, 1).unwrap());
+  assert!(GMonthDay::new(1, 31).unwrap() < GMonthDay::new(2, 1).unwrap());
+
+  let parse = |value: &str| value.parse::<GYearMonth>().unwrap();
+  assert!(parse("2024-01+01:00") < parse("2024-01Z"));
+  assert!(parse("2024-01Z") < parse("2024-02"));
+  assert_eq!(parse("2024-01").partial_cmp(&parse("2024-01Z")), None);
+  assert!(GYear::new(-44) < GYear::new(2024).with_timezone(600).unwrap());
+  let parse = |value: &str| value.parse::<GMonthDay>().unwrap();
+  assert!(parse("--02-29") < parse("--03-01"));
+  assert_eq!(
+    parse("--03-01+14:00").partial_cmp(&parse("--02-29-10:00")),
+    None
+  );
+}
+
+#[test]
+fn xsd_duration() {
+  init();
+
+  #[derive(Debug, PartialEq, YaDeserialize, YaSerialize)]
+  #[yaserde(rename = "task")]
+  pub struct Task {
+    #[yaserde(attribute = true)]
+    timeout: Duration,
+    retention: Duration,
+    delay: Option<Duration>,
+  }
+
+  let model = Task {
+    timeout: Duration::new(0, 90, 500_000_000).unwrap(),
+    retention: Duration::new(14, 3 * 86400 + 4 * 3600, 0).unwrap(),
+    delay: Some(Duration::new(0, 15 * 60, 0).unwrap().negate()),
+  };
+  let content =
+    r#"<task timeout="PT1M30.5S"><retention>P1Y2M3DT4H</retention><delay>-PT15M</delay></task>"#;
+
+  serialize_and_validate!(model, content);
+  deserialize_and_validate!(content, model, Task);
+
+  let parse = |value: &str| value.parse::<Duration>();
+
+  assert_eq!(parse("PT36H").unwrap().to_string(), "P1DT12H");
+  assert_eq!(parse("P0D").unwrap().to_string(), "PT0S");
+  assert_eq!(parse("-P0D").unwrap(), Duration::default());
+  assert_eq!(parse("P12M").unwrap(), parse("P1Y").unwrap());
+  assert_eq!(parse("PT0.000000001S").unwrap().subsec_nanos(), 1);
+  assert_eq!(parse("PT5.S").unwrap(), parse("PT5S").unwrap());
+  assert_eq!(parse("PT.5S").unwrap().to_string(), "PT0.5S");
+  for invalid in [
+    "", "P", "PT", "P1DT", "1D", "P1H", "PT1D", "P1M1Y", "P-1D", "PT1.5M", "PT.S", "PTS",
+  ] {
+    assert_eq!(
+      parse(invalid),
+      Err(format!("Invalid duration {:?}", invalid))
+    );
+  }
+  assert!(parse("P99999999999999999999Y").is_err());
+
+  assert!(parse("P1D").unwrap() < parse("PT25H").unwrap());
+  assert!(parse("-P1Y").unwrap() < parse("P1M").unwrap());
+  assert!(parse("P1M").unwrap() < parse("P1M1D").unwrap());
+  assert_eq!(
+    parse("P1M").unwrap().partial_cmp(&parse("P30D").unwrap()),
+    None
+  );
+  // a month has at most 31 days, and a year at most 366
+  assert!(parse("P1M").unwrap() < parse("P32D").unwrap());
+  assert!(parse("-P1M").unwrap() > parse("-P32D").unwrap());
+  assert!(parse("P1Y").unwrap() > parse("P364D").unwrap());
+  assert_eq!(
+    parse("P1Y").unwrap().partial_cmp(&parse("P365D").unwrap()),
+    None
+  );
+  assert!(parse("P400Y").unwrap() < parse("P146097DT1S").unwrap());
+
+  assert_eq!(
+    parse("PT1.500000000000S").unwrap(),
+    parse("PT1.5S").unwrap()
+  );
+  assert_eq!(
+    parse("PT1.0000000001S"),
+    Err("Duration \"PT1.0000000001S\" is more precise than nanoseconds".to_string())
+  );
+
+  assert_eq!(
+    std::time::Duration::try_from(parse("P1DT0.25S").unwrap()),
+    Ok(std::time::Duration::new(86400, 250_000_000))
+  );
+  assert!(std::time::Duration::try_from(parse("P1M").unwrap()).is_err());
+  assert!(std::time::Duration::try_from(parse("-PT1S").unwrap()).is_err());
+  assert_eq!(
+    Duration::from(std::time::Duration::from_millis(1500)).to_string(),
+    "PT1.5S"
+  );
+}
@@ -1,36 +1,103 @@
-use proc_macro2::{Ident, TokenStream};
+use crate::common::{Field, YaSerdeField};
+use heck::{
+  ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+  ToUpperCamelCase,
+};
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use serde::Deserialize;
 use serde_tokenstream::from_tokenstream;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
-use syn::{Attribute, Meta};
+use syn::ext::IdentExt;
+use syn::{Attribute, DataEnum, Fields, LitStr, Meta, Variant};
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
 
 #[derive(Clone, Debug, Default, PartialEq, Deserialize)]
 pub struct YaSerdeAttribute {
+  /// Other names accepted on deserialization, can be repeated
+  #[serde(skip)]
+  pub alias: Vec<String>,
   /// Set this field as an XML attribute
   #[serde(default)]
   pub attribute: bool,
+  /// Read and write a byte buffer as `xs:base64Binary`
+  #[serde(default)]
+  pub base64: bool,
+  /// Name of the sibling element holding the payload of an adjacently tagged enum
+  #[serde(default)]
+  pub content: Option<String>,
   /// Set default callback function
   #[serde(default)]
   pub default: Option<String>,
+  /// Set by a bare `default`, which takes the value from `Default::default()`
+  #[serde(skip)]
+  pub default_trait: bool,
   /// Set the default namespace
   #[serde(default)]
   pub default_namespace: Option<String>,
+  /// Fail on unexpected child elements, attributes and text content
+  #[serde(default)]
+  pub deny_unknown_fields: bool,
+  /// Read the field with this function instead of its `YaDeserialize` implementation
+  #[serde(default)]
+  pub deserialize_with: Option<String>,
   /// Flatten child fields
   #[serde(default)]
   pub flatten: bool,
+  /// Read and write a byte buffer as `xs:hexBinary`
+  #[serde(default)]
+  pub hex: bool,
+  /// Read and write a list as a single element holding an `xs:list` value
+  #[serde(default)]
+  pub list: bool,
+  /// Wrap `base64` values in lines of at most this number of characters
+  #[serde(default)]
+  pub line_length: Option<usize>,
   /// Declare all namespaces with prefix/URL
   #[serde(default)]
   pub namespaces: BTreeMap<String, String>,
+  /// Module declared with `yaserde::namespaces!`, holding the namespaces shared by many types
+  #[serde(skip)]
+  pub namespaces_table: Option<syn::Path>,
+  /// Catch unknown enum values in this variant, which is either a unit variant or keeps them in a `String`
+  #[serde(default)]
+  pub other: bool,
   /// Set the prefix for the scope
   #[serde(default)]
   pub prefix: Option<String>,
   /// Rename the field/struct/enum name
   #[serde(default)]
   pub rename: Option<String>,
+  /// Case convention for the names of fields and variants which are not renamed
+  #[serde(default)]
+  pub rename_all: Option<String>,
+  /// Case convention for the names of attribute fields, instead of `rename_all`
+  #[serde(default)]
+  pub rename_all_attributes: Option<String>,
+  /// Write the field with this function instead of its `YaSerialize` implementation
+  #[serde(default)]
+  pub serialize_with: Option<String>,
+  /// Separator used to split and join list-valued attributes and text content
+  #[serde(default)]
+  pub separator: Option<String>,
+  /// Name of the attribute or element holding the discriminator of a tagged enum
   #[serde(default)]
   pub tag: Option<String>,
+  /// Where the enum `tag` is read and written: `"attribute"` (default) or `"element"`
+  #[serde(default)]
+  pub tag_kind: Option<String>,
+  /// Deserialize an enum by trying each variant in order, and serialize it without discriminator
+  #[serde(default)]
+  pub untagged: bool,
+  /// Disable both the serialization and the deserialization for the field
+  #[serde(default)]
+  pub skip: bool,
+  /// Disable the deserialization for the field, which is set from its default value
+  #[serde(default)]
+  pub skip_deserializing: bool,
   /// Disable the serialization for the field
   #[serde(default)]
   pub skip_serializing: bool,
@@ -40,9 +107,21 @@ pub struct YaSerdeAttribute {
   /// Set the field as an XML text content
   #[serde(default)]
   pub text: bool,
+  /// Trim whitespace around the items of a list split with `separator`
+  #[serde(default)]
+  pub trim: bool,
   /// Set the field as an XML CDATA content
   #[serde(default)]
   pub cdata: bool,
+  /// Read and write the items of a list through a wrapper element
+  #[serde(default)]
+  pub wrapper: Option<String>,
+  /// Module providing both the `serialize` and `deserialize` functions of the field
+  #[serde(default)]
+  pub with: Option<String>,
+  /// Write the wrapper element even when the list is empty
+  #[serde(default)]
+  pub emit_empty_wrapper: bool,
 }
 
 impl TryFrom<&Attribute> for YaSerdeAttribute {
@@ -57,9 +136,19 @@ impl TryFrom<&Attribute> for YaSerdeAttribute {
         Meta::List(list) => {
           let mut tokens = TokenStream::new();
           list.tokens.to_tokens(&mut tokens);
+          let (tokens, alias) = take_aliases(tokens);
+          let (tokens, default_trait) = take_flag(tokens, "default");
+          let (tokens, deny_unknown_fields) = take_flag(tokens, "deny_unknown_fields");
+          let (tokens, namespaces_table) = take_namespaces_table(tokens);
 
           match from_tokenstream::<YaSerdeAttribute>(&tokens) {
-            Ok(attribute) => attribute,
+            Ok(attribute) => YaSerdeAttribute {
+              alias,
+              default_trait,
+              namespaces_table,
+              deny_unknown_fields: deny_unknown_fields || attribute.deny_unknown_fields,
+              ..attribute
+            },
             Err(error) => {
               panic!("YaSerDe derive error: {}", error);
             }
@@ -77,6 +166,93 @@ impl TryFrom<&Attribute> for YaSerdeAttribute {
   }
 }
 
+/// Extract the `alias` values, which the derived `Deserialize` would reject when repeated
+fn take_aliases(tokens: TokenStream) -> (TokenStream, Vec<String>) {
+  let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+  let mut aliases = vec![];
+
+  let items: Vec<&[TokenTree]> = tokens
+    .split(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+    .filter(|item| {
+      if let [TokenTree::Ident(ident), TokenTree::Punct(punct), TokenTree::Literal(literal)] = item
+      {
+        if ident == "alias" && punct.as_char() == '=' {
+          let alias = syn::parse2::<LitStr>(literal.to_token_stream())
+            .unwrap_or_else(|error| panic!("YaSerDe derive error: alias {}", error));
+          aliases.push(alias.value());
+          return false;
+        }
+      }
+      !item.is_empty()
+    })
+    .collect();
+
+  (join_items(&items), aliases)
+}
+
+/// Extract a bare `name` item, which the derived `Deserialize` would reject without a value
+fn take_flag(tokens: TokenStream, name: &str) -> (TokenStream, bool) {
+  let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+  let mut found = false;
+
+  let items: Vec<&[TokenTree]> = tokens
+    .split(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+    .filter(|item| {
+      if let [TokenTree::Ident(ident)] = item {
+        if ident == name {
+          found = true;
+          return false;
+        }
+      }
+      !item.is_empty()
+    })
+    .collect();
+
+  (join_items(&items), found)
+}
+
+/// Extract `namespaces = path`, which refers to a shared table instead of listing the namespaces
+fn take_namespaces_table(tokens: TokenStream) -> (TokenStream, Option<syn::Path>) {
+  let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+  let mut table = None;
+
+  let items: Vec<&[TokenTree]> = tokens
+    .split(|token| matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+    .filter(|item| {
+      if let [TokenTree::Ident(ident), TokenTree::Punct(punct), value @ ..] = item {
+        let is_map =
+          matches!(value, [TokenTree::Group(group)] if group.delimiter() == Delimiter::Brace);
+        if ident == "namespaces" && punct.as_char() == '=' && !is_map {
+          let path = syn::parse2::<syn::Path>(value.iter().cloned().collect())
+            .unwrap_or_else(|error| panic!("YaSerDe derive error: namespaces {}", error));
+          table = Some(path);
+          return false;
+        }
+      }
+      !item.is_empty()
+    })
+    .collect();
+
+  (join_items(&items), table)
+}
+
+fn join_items(items: &[&[TokenTree]]) -> TokenStream {
+  items
+    .iter()
+    .enumerate()
+    .flat_map(|(index, item)| {
+      let separator = (index > 0).then(|| quote!(,));
+      separator.into_iter().flatten().chain(item.iter().cloned())
+    })
+    .collect()
+}
+
+/// Parse the path of a function given in an attribute, e.g. `"crate::defaults::port"`
+pub fn function_path(path: &str) -> syn::Path {
+  syn::parse_str(path)
+    .unwrap_or_else(|_| panic!("YaSerDe derive error: invalid function path {:?}", path))
+}
+
 impl From<&Vec<Attribute>> for YaSerdeAttribute {
   fn from(attributes: &Vec<Attribute>) -> Self {
     attributes
@@ -87,10 +263,295 @@ impl From<&Vec<Attribute>> for YaSerdeAttribute {
 }
 
 impl YaSerdeAttribute {
+  /// Function building the default value of type `ty`, from `default = "path"` or a bare `default`
+  pub fn default_function(&self, ty: impl ToTokens) -> Option<TokenStream> {
+    match (&self.default, self.default_trait) {
+      (Some(_), true) => panic!("YaSerDe derive error: default is set twice"),
+      (Some(path), false) => {
+        let path = function_path(path);
+        Some(quote!(#path))
+      }
+      (None, true) => Some(quote!(<#ty as ::std::default::Default>::default)),
+      (None, false) => None,
+    }
+  }
+
+  /// URI of the namespace declared for `prefix`, in `namespaces` or in the shared table
+  pub fn namespace_uri(&self, prefix: &str) -> Option<NamespaceUri> {
+    if let Some(uri) = self.namespaces.get(prefix) {
+      return Some(NamespaceUri::literal(uri));
+    }
+
+    self.namespaces_table.as_ref().map(|table| {
+      let constant = syn::parse_str::<Ident>(prefix).unwrap_or_else(|_| {
+        panic!(
+          "YaSerDe derive error: prefix {:?} is not an identifier, it can not be read from the \
+           namespaces table",
+          prefix
+        )
+      });
+
+      NamespaceUri {
+        key: format!("{}::{}", table.to_token_stream(), prefix),
+        tokens: quote!(#table::#constant),
+      }
+    })
+  }
+
+  /// Namespace of `prefix`, declared in `namespaces` or in the shared table, or else resolved when
+  /// reading, see `NamespaceUri::undeclared`
+  pub fn resolve_prefix(&self, prefix: &str) -> NamespaceUri {
+    self
+      .namespace_uri(prefix)
+      .unwrap_or_else(|| NamespaceUri::undeclared(prefix))
+  }
+
+  /// Namespace of an attribute with this `prefix`.
+  ///
+  /// An unprefixed attribute is in no namespace. The reserved `xml` and `xmlns` prefixes are
+  /// always bound to their namespace.
+  pub fn attribute_namespace(&self, prefix: Option<&str>) -> Option<NamespaceUri> {
+    let namespace = match prefix? {
+      "xml" if !self.namespaces.contains_key("xml") => NamespaceUri::literal(XML_NAMESPACE),
+      "xmlns" => NamespaceUri::literal(XMLNS_NAMESPACE),
+      prefix => self.resolve_prefix(prefix),
+    };
+    Some(namespace)
+  }
+
+  /// Namespace and local name of the attribute holding the `tag` of an enum
+  pub fn tag_attribute_name(&self) -> (Option<NamespaceUri>, String) {
+    let tag = self.tag.clone().unwrap_or_default();
+    match tag.split_once(':') {
+      Some((prefix, local_name)) => (
+        self.attribute_namespace(Some(prefix)),
+        local_name.to_string(),
+      ),
+      None => (None, tag),
+    }
+  }
+
+  /// Pattern matching the `(namespace, local name)` of the attribute holding the `tag` of an enum
+  pub fn tag_attribute_pattern(&self) -> TokenStream {
+    let (namespace, local_name) = self.tag_attribute_name();
+
+    attribute_name_pattern(namespace, &local_name)
+  }
+
   pub fn xml_element_name(&self, ident: &Ident) -> String {
     self.rename.clone().unwrap_or_else(|| ident.to_string())
   }
 
+  /// Name of a field or variant following the `rename_all` (or `rename_all_attributes`) convention
+  pub fn rename_all(&self, ident: &Ident, is_attribute: bool) -> Option<String> {
+    let convention = if is_attribute {
+      self
+        .rename_all_attributes
+        .as_ref()
+        .or(self.rename_all.as_ref())
+    } else {
+      self.rename_all.as_ref()
+    }?;
+    let name = ident.unraw().to_string();
+
+    Some(match convention.as_str() {
+      "lowercase" => name.to_lowercase(),
+      "UPPERCASE" => name.to_uppercase(),
+      "PascalCase" => name.to_upper_camel_case(),
+      "camelCase" => name.to_lower_camel_case(),
+      "snake_case" => name.to_snake_case(),
+      "SCREAMING_SNAKE_CASE" => name.to_shouty_snake_case(),
+      "kebab-case" => name.to_kebab_case(),
+      "SCREAMING-KEBAB-CASE" => name.to_shouty_kebab_case(),
+      convention => panic!(
+        "YaSerDe derive error: unknown case convention {:?}, expected one of lowercase, \
+         UPPERCASE, PascalCase, camelCase, snake_case, SCREAMING_SNAKE_CASE, kebab-case or \
+         SCREAMING-KEBAB-CASE",
+        convention
+      ),
+    })
+  }
+
+  /// Attributes of a variant of this enum, named following `rename_all` unless renamed
+  pub fn variant_attributes(&self, variant: &Variant) -> YaSerdeAttribute {
+    let mut attributes = YaSerdeAttribute::from(&variant.attrs);
+    if attributes.rename.is_none() {
+      attributes.rename = self.rename_all(&variant.ident, false);
+    }
+    attributes
+  }
+
+  /// `alias` values, followed by their local names when they are prefixed
+  pub fn alias_names(&self) -> Vec<String> {
+    let local_names = self.alias.iter().filter_map(|alias| {
+      alias
+        .split_once(':')
+        .map(|(_prefix, name)| name.to_string())
+    });
+
+    let mut names: Vec<String> = self.alias.iter().cloned().chain(local_names).collect();
+    names.dedup();
+    names
+  }
+
+  /// Fail when an `alias` of a variant matches the name or an alias of another one
+  pub fn check_variant_aliases(&self, data_enum: &DataEnum) {
+    let mut names: BTreeMap<String, &Ident> = BTreeMap::new();
+    for variant in &data_enum.variants {
+      names.insert(
+        self
+          .variant_attributes(variant)
+          .xml_element_name(&variant.ident),
+        &variant.ident,
+      );
+    }
+
+    for variant in &data_enum.variants {
+      for alias in self.variant_attributes(variant).alias_names() {
+        if let Some(other) = names.insert(alias.clone(), &variant.ident) {
+          if other != &variant.ident {
+            panic!(
+              "YaSerDe derive error: alias {:?} of variant {} collides with variant {}",
+              alias, variant.ident, other
+            );
+          }
+        }
+      }
+    }
+  }
+
+  pub fn is_tag_element(&self) -> bool {
+    match self.tag_kind.as_deref() {
+      None | Some("attribute") => false,
+      Some("element") => true,
+      Some(kind) => panic!("YaSerDe derive error: unknown tag_kind {:?}", kind),
+    }
+  }
+
+  pub fn is_adjacently_tagged(&self) -> bool {
+    self.tag.is_some() && (self.content.is_some() || self.is_tag_element())
+  }
+
+  /// Namespace of the unprefixed child elements, no namespace without `default_namespace`
+  pub fn default_namespace_uri(&self) -> NamespaceUri {
+    self
+      .default_namespace
+      .as_ref()
+      .map(|prefix| self.resolve_prefix(prefix))
+      .unwrap_or_else(NamespaceUri::none)
+  }
+
+  /// Namespace of the element of the container, the one of its `prefix` or the default namespace
+  pub fn container_namespace_uri(&self) -> NamespaceUri {
+    match &self.prefix {
+      Some(prefix) => self.resolve_prefix(prefix),
+      None => self.default_namespace_uri(),
+    }
+  }
+
+  /// Namespace and local name of a child element named by an attribute of the container, such as
+  /// the `tag` and `content` elements of an enum; an unprefixed name is in the default namespace
+  pub fn child_namespace_and_name(&self, name: &str) -> (NamespaceUri, String) {
+    match name.split_once(':') {
+      Some((prefix, local_name)) => (self.resolve_prefix(prefix), local_name.to_string()),
+      None => (self.default_namespace_uri(), name.to_string()),
+    }
+  }
+
+  /// Fieldless enums without discriminator are read and written as their variant name
+  pub fn is_value_enum(&self, data_enum: &DataEnum) -> bool {
+    self.tag.is_none()
+      && !self.untagged
+      && !self.flatten
+      && !data_enum.variants.is_empty()
+      && data_enum.variants.iter().all(|variant| {
+        matches!(variant.fields, Fields::Unit) || YaSerdeAttribute::from(&variant.attrs).other
+      })
+  }
+
+  /// Compile errors for the variants an untagged or adjacently tagged enum can't read and write:
+  /// only unit variants and variants holding a single value, which is not a `Vec` or a nested
+  /// `Option`, are supported
+  pub fn check_single_value_variants(data_enum: &DataEnum, kind: &str) -> Option<TokenStream> {
+    data_enum
+      .variants
+      .iter()
+      .filter_map(|variant| {
+        let fields = match &variant.fields {
+          Fields::Unit => return None,
+          Fields::Named(_) => "named fields",
+          Fields::Unnamed(fields) if fields.unnamed.len() > 1 => "multiple fields",
+          Fields::Unnamed(fields) => {
+            match YaSerdeField::new(fields.unnamed[0].clone()).get_type() {
+              Field::FieldVec { .. } => "a Vec",
+              Field::FieldOption { data_type }
+                if matches!(
+                  *data_type,
+                  Field::FieldOption { .. } | Field::FieldVec { .. }
+                ) =>
+              {
+                "a nested Option or Vec"
+              }
+              _ => return None,
+            }
+          }
+        };
+
+        Some(syn::Error::new_spanned(
+          variant,
+          format!(
+            "YaSerDe derive error: {} enum variant {} with {} is not supported",
+            kind, variant.ident, fields
+          ),
+        ))
+      })
+      .reduce(|mut errors, error| {
+        errors.combine(error);
+        errors
+      })
+      .map(|errors| errors.to_compile_error())
+  }
+
+  /// An untagged enum tries each variant in turn, it has no unknown variant to catch
+  pub fn check_untagged_other(data_enum: &DataEnum) {
+    if let Some((label, _keeps_value)) = Self::other_variant(data_enum) {
+      panic!(
+        "YaSerDe derive error: untagged enum can't have an other variant, found {}",
+        label
+      );
+    }
+  }
+
+  /// Find the `other` variant of an enum, and whether it keeps the unknown value
+  pub fn other_variant(data_enum: &DataEnum) -> Option<(&Ident, bool)> {
+    let mut variants = data_enum
+      .variants
+      .iter()
+      .filter(|variant| YaSerdeAttribute::from(&variant.attrs).other);
+
+    let variant = variants.next()?;
+    if variants.next().is_some() {
+      panic!("YaSerDe derive error: only one enum variant can be marked as other");
+    }
+
+    match variant.fields {
+      Fields::Unit => Some((&variant.ident, false)),
+      Fields::Unnamed(ref fields)
+        if fields.unnamed.len() == 1
+          && matches!(
+            YaSerdeField::new(fields.unnamed[0].clone()).get_type(),
+            Field::FieldString
+          ) =>
+      {
+        Some((&variant.ident, true))
+      }
+      _ => panic!(
+        "YaSerDe derive error: other variant {} must be a unit variant or hold a String",
+        variant.ident
+      ),
+    }
+  }
+
   pub fn prefix_namespace(&self) -> String {
     if self.default_namespace == self.prefix {
       "".to_string()
@@ -104,27 +565,27 @@ impl YaSerdeAttribute {
 
   pub fn get_namespace_matching(
     &self,
-    prefix: &Option<String>,
+    prefixes: &[Option<String>],
     element_namespace: TokenStream,
     element_name: TokenStream,
     take_root_prefix: bool,
   ) -> TokenStream {
-    let configured_prefix = if take_root_prefix {
-      self.prefix.clone()
+    let configured_prefixes = if take_root_prefix {
+      vec![self
+        .prefix
+        .clone()
+        .or_else(|| self.default_namespace.clone())]
     } else {
-      prefix.clone()
+      prefixes.to_vec()
     };
 
-    let namespaces_matches: TokenStream = self
-      .namespaces
+    let namespaces_matches: TokenStream = configured_prefixes
+      .into_iter()
+      .flatten()
+      .collect::<BTreeSet<String>>()
       .iter()
-      .filter_map(|(prefix, namespace)| {
-        if configured_prefix.as_deref().eq(&Some(prefix)) {
-          Some(quote!(#namespace => {}))
-        } else {
-          None
-        }
-      })
+      .map(|prefix| self.resolve_prefix(prefix))
+      .map(|namespace| quote!(#namespace => {}))
       .collect();
 
     quote!(
@@ -141,3 +602,56 @@ impl YaSerdeAttribute {
     )
   }
 }
+
+/// Pattern matching the `(namespace, local name)` of an attribute, see `attribute_namespace`
+pub fn attribute_name_pattern(namespace: Option<NamespaceUri>, name: &str) -> TokenStream {
+  match namespace {
+    Some(namespace) => quote!((::std::option::Option::Some(#namespace), #name)),
+    None => quote!((::std::option::Option::None, #name)),
+  }
+}
+
+/// URI of a namespace, a literal or a constant of a namespaces table
+///
+/// The URI of a prefix which is not declared by the type is only known when reading, from the
+/// deserializer config or from the document. It is matched as the prefix after a `\0`, which is the namespace given to the names
+/// bound to it by `Deserializer::__resolve_namespace`.
+#[derive(Clone, Debug)]
+pub struct NamespaceUri {
+  key: String,
+  tokens: TokenStream,
+}
+
+impl NamespaceUri {
+  pub fn literal(uri: &str) -> Self {
+    NamespaceUri {
+      key: uri.to_string(),
+      tokens: quote!(#uri),
+    }
+  }
+
+  /// No namespace, matched by the empty URI
+  pub fn none() -> Self {
+    Self::literal("")
+  }
+
+  pub fn undeclared(prefix: &str) -> Self {
+    Self::literal(&format!("\0{}", prefix))
+  }
+
+  /// Prefix of a namespace resolved when reading
+  pub fn undeclared_prefix(&self) -> Option<&str> {
+    self.key.strip_prefix('\0')
+  }
+
+  /// Identifies the namespace while expanding the derive, when the URI itself is not known
+  pub fn key(&self) -> &str {
+    &self.key
+  }
+}
+
+impl ToTokens for NamespaceUri {
+  fn to_tokens(&self, tokens: &mut TokenStream) {
+    self.tokens.to_tokens(tokens)
+  }
+}
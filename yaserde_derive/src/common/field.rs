@@ -1,4 +1,6 @@
-use crate::common::attribute::YaSerdeAttribute;
+use crate::common::attribute::{
+  attribute_name_pattern, function_path, NamespaceUri, YaSerdeAttribute,
+};
 use heck::ToUpperCamelCase;
 use proc_macro2::Span;
 use proc_macro2::{Ident, TokenStream};
@@ -18,10 +20,42 @@ impl YaSerdeField {
   pub fn new(syn_field: syn::Field) -> Self {
     let attributes = YaSerdeAttribute::from(&syn_field.attrs);
 
-    YaSerdeField {
+    let field = YaSerdeField {
       syn_field,
       attributes,
+    };
+    field.check_list_options();
+    field
+  }
+
+  /// `separator` and `trim` only apply to the items of a list value.
+  fn check_list_options(&self) {
+    if self.attributes.separator.is_none() && !self.attributes.trim {
+      return;
+    }
+
+    let is_vec = match self.get_type() {
+      Field::FieldVec { .. } => true,
+      Field::FieldOption { data_type } => matches!(*data_type, Field::FieldVec { .. }),
+      _ => false,
+    };
+    let is_list_value = self.is_attribute() || self.is_text_content() || self.is_list();
+
+    if !is_vec || !is_list_value || self.is_binary() {
+      panic!(
+        "YaSerDe derive error: separator and trim can only be used on a Vec attribute, text content or list element"
+      );
+    }
+  }
+
+  /// Name the field following the `rename_all` convention of its struct, unless it is renamed.
+  pub fn with_container(mut self, root_attributes: &YaSerdeAttribute) -> Self {
+    if self.attributes.rename.is_none() {
+      if let Some(ident) = &self.syn_field.ident {
+        self.attributes.rename = root_attributes.rename_all(ident, self.attributes.attribute);
+      }
     }
+    self
   }
 
   pub fn is_attribute(&self) -> bool {
@@ -36,18 +70,142 @@ impl YaSerdeField {
     self.attributes.flatten
   }
 
+  pub fn is_list(&self) -> bool {
+    self.attributes.list
+  }
+
+  /// Byte buffers encoded with `base64` or `hex` are read and written as a string.
+  pub fn is_binary(&self) -> bool {
+    match (self.attributes.base64, self.attributes.hex) {
+      (true, true) => panic!("YaSerDe derive error: base64 and hex can't be used together"),
+      (false, false) if self.attributes.line_length.is_some() => {
+        panic!("YaSerDe derive error: line_length can only be used with base64")
+      }
+      (base64, hex) => base64 || hex,
+    }
+  }
+
+  /// Encode the bytes of `value` into a `String`.
+  pub fn encode_binary(&self, value: TokenStream) -> TokenStream {
+    if self.attributes.hex {
+      quote!(::yaserde::binary::encode_hex(::std::convert::AsRef::<[u8]>::as_ref(#value)))
+    } else {
+      let line_length = match self.attributes.line_length {
+        Some(line_length) => quote!(::std::option::Option::Some(#line_length)),
+        None => quote!(::std::option::Option::None),
+      };
+
+      quote!(::yaserde::binary::encode_base64(
+        ::std::convert::AsRef::<[u8]>::as_ref(#value),
+        #line_length,
+      ))
+    }
+  }
+
+  /// Decode the `&str` `value` into the byte buffer type of the field.
+  pub fn decode_binary(&self, value: TokenStream) -> TokenStream {
+    let decode = if self.attributes.hex {
+      quote!(::yaserde::binary::decode_hex)
+    } else {
+      quote!(::yaserde::binary::decode_base64)
+    };
+
+    quote!(#decode(#value).and_then(::yaserde::binary::FromBytes::from_bytes))
+  }
+
+  pub fn is_option(&self) -> bool {
+    match &self.syn_field.ty {
+      Path(path) => path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Option"),
+      _ => false,
+    }
+  }
+
+  /// Function writing the field, from `serialize_with` or the `serialize` function of `with`.
+  pub fn get_serialize_with(&self) -> Option<syn::Path> {
+    self.adapter(
+      &self.attributes.serialize_with,
+      "serialize_with",
+      "serialize",
+    )
+  }
+
+  /// Function reading the field, from `deserialize_with` or the `deserialize` function of `with`.
+  pub fn get_deserialize_with(&self) -> Option<syn::Path> {
+    self.adapter(
+      &self.attributes.deserialize_with,
+      "deserialize_with",
+      "deserialize",
+    )
+  }
+
+  fn adapter(&self, function: &Option<String>, name: &str, suffix: &str) -> Option<syn::Path> {
+    let path = match (function, &self.attributes.with) {
+      (Some(_), Some(_)) => panic!(
+        "YaSerDe derive error: with and {} can't be used together",
+        name
+      ),
+      (Some(function), None) => function.clone(),
+      (None, Some(module)) => format!("{}::{}", module, suffix),
+      (None, None) => return None,
+    };
+
+    if self.is_binary() || self.is_list() || self.is_flatten() || self.attributes.wrapper.is_some()
+    {
+      panic!(
+        "YaSerDe derive error: {} or with can't be used with base64, hex, list, flatten or wrapper",
+        name
+      );
+    }
+
+    Some(function_path(&path))
+  }
+
   pub fn label(&self) -> Option<Ident> {
     self.syn_field.ident.clone()
   }
 
   pub fn is_skip_serializing(&self) -> bool {
-    self.attributes.skip_serializing
+    self.attributes.skip_serializing || self.attributes.skip
+  }
+
+  pub fn is_skip_deserializing(&self) -> bool {
+    self.attributes.skip_deserializing || self.attributes.skip
+  }
+
+  pub fn get_prefix(&self) -> Option<String> {
+    self.attributes.prefix.clone()
   }
 
   pub fn is_cdata(&self) -> bool {
     self.attributes.cdata
   }
 
+  pub fn get_list_separator(&self) -> String {
+    self
+      .attributes
+      .separator
+      .clone()
+      .unwrap_or_else(|| " ".to_string())
+  }
+
+  /// Empty items are only dropped when they are trimmed, but an empty value is always an empty
+  /// list, as written for an empty `Vec`.
+  pub fn split_list(&self, value: TokenStream) -> TokenStream {
+    match &self.attributes.separator {
+      Some(separator) if self.attributes.trim => quote! {
+        #value.split(#separator).map(|item| item.trim()).filter(|item| !item.is_empty())
+      },
+      Some(separator) => quote! {
+        (!#value.is_empty()).then(|| #value.split(#separator)).into_iter().flatten()
+      },
+      None => quote!(#value.split_whitespace()),
+    }
+  }
+
   pub fn get_value_label(&self) -> Option<syn::Ident> {
     self
       .syn_field
@@ -80,6 +238,46 @@ impl YaSerdeField {
     format!("{}{}", prefix, label)
   }
 
+  pub fn is_emit_empty_wrapper(&self) -> bool {
+    self.attributes.emit_empty_wrapper
+  }
+
+  /// Prefix and local name of the wrapper element, the prefix defaults to the one of the field.
+  fn wrapper_prefix_and_name(&self) -> Option<(Option<String>, String)> {
+    self
+      .attributes
+      .wrapper
+      .as_ref()
+      .map(|wrapper| match wrapper.split_once(':') {
+        Some((prefix, name)) => (Some(prefix.to_string()), name.to_string()),
+        None => (self.attributes.prefix.clone(), wrapper.clone()),
+      })
+  }
+
+  pub fn renamed_wrapper_label(&self, root_attributes: &YaSerdeAttribute) -> Option<String> {
+    self.wrapper_prefix_and_name().map(|(prefix, name)| {
+      if root_attributes.default_namespace == prefix {
+        name
+      } else {
+        prefix.map_or(name.clone(), |prefix| format!("{}:{}", prefix, name))
+      }
+    })
+  }
+
+  pub fn wrapper_namespace_and_name(
+    &self,
+    root_attributes: &YaSerdeAttribute,
+  ) -> Option<(NamespaceUri, String)> {
+    self.wrapper_prefix_and_name().map(|(prefix, name)| {
+      let namespace = match &prefix {
+        Some(prefix) => root_attributes.resolve_prefix(prefix),
+        None => root_attributes.default_namespace_uri(),
+      };
+
+      (namespace, name)
+    })
+  }
+
   pub fn get_visitor_ident(&self, struct_name: Option<&syn::Path>) -> Ident {
     let label = self.renamed_label_without_namespace();
 
@@ -107,9 +305,16 @@ impl YaSerdeField {
       .then_some("Attribute_".to_string())
       .unwrap_or_default();
 
+    let wrapper = self
+      .attributes
+      .wrapper
+      .as_ref()
+      .map(|w| format!("{}_", w.replace([':', '.'], "_").to_upper_camel_case()))
+      .unwrap_or_default();
+
     Ident::new(
       &format!(
-        "__Visitor_{attribute}{}{}_{}",
+        "__Visitor_{attribute}{wrapper}{}{}_{}",
         prefix,
         label.replace('.', "_").to_upper_camel_case(),
         struct_id
@@ -119,6 +324,16 @@ impl YaSerdeField {
   }
 
   pub fn get_type(&self) -> Field {
+    if self.is_binary() {
+      return if self.is_option() {
+        Field::FieldOption {
+          data_type: Box::new(Field::FieldString),
+        }
+      } else {
+        Field::FieldString
+      };
+    }
+
     Field::from(&self.syn_field)
   }
 
@@ -126,12 +341,8 @@ impl YaSerdeField {
     self.syn_field.span()
   }
 
-  pub fn get_default_function(&self) -> Option<Ident> {
-    self
-      .attributes
-      .default
-      .as_ref()
-      .map(|default| Ident::new(default, self.get_span()))
+  pub fn get_default_function(&self) -> Option<TokenStream> {
+    self.attributes.default_function(&self.syn_field.ty)
   }
 
   pub fn get_skip_serializing_if_function(&self) -> Option<Ident> {
@@ -142,18 +353,121 @@ impl YaSerdeField {
       .map(|skip_serializing_if| Ident::new(skip_serializing_if, self.get_span()))
   }
 
-  pub fn prefix_namespace(&self, root_attributes: &YaSerdeAttribute) -> String {
-    root_attributes
-      .namespaces
+  /// Namespace of the field element, the default namespace when it has no prefix
+  pub fn prefix_namespace(&self, root_attributes: &YaSerdeAttribute) -> NamespaceUri {
+    match &self.attributes.prefix {
+      Some(prefix) => root_attributes.resolve_prefix(prefix),
+      None => root_attributes.default_namespace_uri(),
+    }
+  }
+
+  /// Prefix and local name of each `alias`, an alias without prefix has the prefix of the field
+  fn alias_prefixes_and_names(&self) -> Vec<(Option<String>, String)> {
+    self
+      .attributes
+      .alias
       .iter()
-      .find_map(|(prefix, namespace)| {
-        if self.attributes.prefix.as_deref().eq(&Some(prefix)) {
-          Some(namespace.clone())
-        } else {
-          None
-        }
+      .map(|alias| match alias.split_once(':') {
+        Some((prefix, name)) => (Some(prefix.to_string()), name.to_string()),
+        None => (self.attributes.prefix.clone(), alias.clone()),
       })
-      .unwrap_or_default()
+      .collect()
+  }
+
+  /// Namespace and local name of each `alias`
+  pub fn aliases(&self, root_attributes: &YaSerdeAttribute) -> Vec<(NamespaceUri, String)> {
+    self
+      .alias_prefixes_and_names()
+      .into_iter()
+      .map(|(prefix, name)| {
+        let namespace = match &prefix {
+          Some(prefix) => root_attributes.resolve_prefix(prefix),
+          None => root_attributes.default_namespace_uri(),
+        };
+
+        (namespace, name)
+      })
+      .collect()
+  }
+
+  /// Pattern matching the namespace and local name of the element, or of one of its aliases
+  pub fn element_pattern(&self, root_attributes: &YaSerdeAttribute) -> TokenStream {
+    let namespace = self.prefix_namespace(root_attributes);
+    let label_name = self.renamed_label_without_namespace();
+    let aliases = self
+      .aliases(root_attributes)
+      .into_iter()
+      .map(|(namespace, name)| quote!(| (#namespace, #name)));
+
+    quote!((#namespace, #label_name) #(#aliases)*)
+  }
+
+  /// Names accepted for the field, reported when `deny_unknown_fields` rejects an input.
+  pub fn accepted_names(&self, root_attributes: &YaSerdeAttribute) -> Vec<String> {
+    if self.is_attribute() {
+      return self
+        .attribute_prefixes_and_names()
+        .into_iter()
+        .map(|(prefix, name)| prefix.map_or(name.clone(), |prefix| format!("{}:{}", prefix, name)))
+        .collect();
+    }
+
+    if let Some((_namespace, name)) = self.wrapper_namespace_and_name(root_attributes) {
+      return vec![name];
+    }
+
+    std::iter::once(self.renamed_label_without_namespace())
+      .chain(
+        self
+          .alias_prefixes_and_names()
+          .into_iter()
+          .map(|(_prefix, name)| name),
+      )
+      .collect()
+  }
+
+  /// Qualified name of the attribute, which keeps its prefix even for the default namespace,
+  /// as the default namespace does not apply to attributes
+  pub fn renamed_attribute_label(&self) -> String {
+    let label = self.renamed_label_without_namespace();
+
+    match &self.attributes.prefix {
+      Some(prefix) => format!("{}:{}", prefix, label),
+      None => label,
+    }
+  }
+
+  fn attribute_prefixes_and_names(&self) -> Vec<(Option<String>, String)> {
+    std::iter::once((
+      self.attributes.prefix.clone(),
+      self.renamed_label_without_namespace(),
+    ))
+    .chain(self.alias_prefixes_and_names())
+    .collect()
+  }
+
+  /// Namespace and local name of the attribute and of each of its aliases, see
+  /// `YaSerdeAttribute::attribute_namespace`
+  pub fn attribute_names(
+    &self,
+    root_attributes: &YaSerdeAttribute,
+  ) -> Vec<(Option<NamespaceUri>, String)> {
+    self
+      .attribute_prefixes_and_names()
+      .into_iter()
+      .map(|(prefix, name)| (root_attributes.attribute_namespace(prefix.as_deref()), name))
+      .collect()
+  }
+
+  /// Pattern matching the namespace and local name of the attribute, or of one of its aliases,
+  /// see `attribute_names`
+  pub fn attribute_pattern(&self, root_attributes: &YaSerdeAttribute) -> TokenStream {
+    let patterns = self
+      .attribute_names(root_attributes)
+      .into_iter()
+      .map(|(namespace, name)| attribute_name_pattern(namespace, &name));
+
+    quote!(#(#patterns)|*)
   }
 
   pub fn get_namespace_matching(
@@ -162,12 +476,18 @@ impl YaSerdeField {
     element_namespace: TokenStream,
     element_name: TokenStream,
   ) -> TokenStream {
-    root_attributes.get_namespace_matching(
-      &self.attributes.prefix,
-      element_namespace,
-      element_name,
-      false,
-    )
+    // Elements without prefix are in the default namespace
+    let prefixes: Vec<Option<String>> = std::iter::once(self.attributes.prefix.clone())
+      .chain(
+        self
+          .alias_prefixes_and_names()
+          .into_iter()
+          .map(|(prefix, _name)| prefix),
+      )
+      .map(|prefix| prefix.or_else(|| root_attributes.default_namespace.clone()))
+      .collect();
+
+    root_attributes.get_namespace_matching(&prefixes, element_namespace, element_name, false)
   }
 
   pub fn ser_wrap_default_attribute(
@@ -206,26 +526,53 @@ impl YaSerdeField {
   }
 }
 
-#[derive(Debug)]
-#[allow(clippy::enum_variant_names)]
-pub enum Field {
-  FieldString,
-  FieldBool,
-  FieldI8,
-  FieldU8,
-  FieldI16,
-  FieldU16,
-  FieldI32,
-  FieldU32,
-  FieldI64,
-  FieldU64,
-  FieldF32,
-  FieldF64,
-  FieldOption { data_type: Box<Field> },
-  FieldVec { data_type: Box<Field> },
-  FieldStruct { struct_name: syn::Path },
+include!("../../../yaserde/src/simple_types.rs");
+
+/// Declares the `Field` variants of the primitive types the derive treats as simple values, from
+/// the entries of `with_simple_types!`: the type identifier matched on the last path segment, the
+/// `Visitor` method used to read it and the fully qualified type.
+macro_rules! simple_types {
+  ($($variant:ident => $ident:literal, $visitor:literal, $type:ty;)*) => {
+    #[derive(Debug)]
+    #[allow(clippy::enum_variant_names)]
+    pub enum Field {
+      $($variant,)*
+      FieldOption { data_type: Box<Field> },
+      FieldVec { data_type: Box<Field> },
+      FieldStruct { struct_name: syn::Path },
+    }
+
+    impl Field {
+      fn from_simple_type_ident(ident: &str) -> Option<Field> {
+        match ident {
+          $($ident => Some(Field::$variant),)*
+          _ => None,
+        }
+      }
+
+      pub fn is_simple_type(&self) -> bool {
+        matches!(self, $(Field::$variant)|*)
+      }
+
+      fn simple_type_tokens(&self) -> Option<TokenStream> {
+        match self {
+          $(Field::$variant => Some(quote! { $type }),)*
+          _ => None,
+        }
+      }
+
+      fn simple_type_visitor(&self) -> Option<&'static str> {
+        match self {
+          $(Field::$variant => Some($visitor),)*
+          _ => None,
+        }
+      }
+    }
+  };
 }
 
+with_simple_types!(simple_types);
+
 impl Field {
   pub fn get_simple_type_visitor(&self) -> Ident {
     format_ident!("visit_{}", self.to_string())
@@ -236,25 +583,13 @@ impl From<&syn::Path> for Field {
   fn from(path: &syn::Path) -> Self {
     let result = if let Some(segment) = path.segments.last() {
       match segment.ident.to_string().as_str() {
-        "String" => Some(Field::FieldString),
-        "bool" => Some(Field::FieldBool),
-        "i8" => Some(Field::FieldI8),
-        "u8" => Some(Field::FieldU8),
-        "i16" => Some(Field::FieldI16),
-        "u16" => Some(Field::FieldU16),
-        "i32" => Some(Field::FieldI32),
-        "u32" => Some(Field::FieldU32),
-        "i64" => Some(Field::FieldI64),
-        "u64" => Some(Field::FieldU64),
-        "f32" => Some(Field::FieldF32),
-        "f64" => Some(Field::FieldF64),
         "Option" => Some(Field::FieldOption {
           data_type: Box::new(Field::from(segment)),
         }),
         "Vec" => Some(Field::FieldVec {
           data_type: Box::new(Field::from(segment)),
         }),
-        _ => None,
+        ident => Field::from_simple_type_ident(ident),
       }
     } else {
       None
@@ -274,6 +609,11 @@ impl From<&syn::Field> for Field {
     }
     match ty {
       Path(ref path) => Field::from(&path.path),
+      syn::Type::Reference(_) => panic!(
+        "YaSerDe derive error: borrowed field {} is not supported, the XML reader owns the \
+         parsed text; use String instead",
+        quote!(#ty)
+      ),
       _ => panic!("unable to match {:?}", field.ty),
     }
   }
@@ -301,41 +641,20 @@ impl From<&syn::PathSegment> for Field {
 
 impl From<Field> for proc_macro2::TokenStream {
   fn from(field: Field) -> proc_macro2::TokenStream {
-    match field {
-      Field::FieldString => quote! { ::std::string::String },
-      Field::FieldBool => quote! { bool },
-      Field::FieldI8 => quote! { i8 },
-      Field::FieldU8 => quote! { u8 },
-      Field::FieldI16 => quote! { i16 },
-      Field::FieldU16 => quote! { u16 },
-      Field::FieldI32 => quote! { i32 },
-      Field::FieldU32 => quote! { u32 },
-      Field::FieldI64 => quote! { i64 },
-      Field::FieldU64 => quote! { u64 },
-      Field::FieldF32 => quote! { f32 },
-      Field::FieldF64 => quote! { f64 },
-      _ => panic!("Not a simple type: {:?}", field),
-    }
+    field
+      .simple_type_tokens()
+      .unwrap_or_else(|| panic!("Not a simple type: {:?}", field))
   }
 }
 
 impl From<&Field> for String {
   fn from(field: &Field) -> String {
     match field {
-      Field::FieldString => "str".to_string(),
-      Field::FieldBool => "bool".to_string(),
-      Field::FieldI8 => "i8".to_string(),
-      Field::FieldU8 => "u8".to_string(),
-      Field::FieldI16 => "i16".to_string(),
-      Field::FieldU16 => "u16".to_string(),
-      Field::FieldI32 => "i32".to_string(),
-      Field::FieldU32 => "u32".to_string(),
-      Field::FieldI64 => "i64".to_string(),
-      Field::FieldU64 => "u64".to_string(),
-      Field::FieldF32 => "f32".to_string(),
-      Field::FieldF64 => "f64".to_string(),
       Field::FieldStruct { struct_name } => quote! {#struct_name}.to_string(),
-      _ => panic!("Not a simple type: {:?}", field),
+      simple_type => simple_type
+        .simple_type_visitor()
+        .unwrap_or_else(|| panic!("Not a simple type: {:?}", field))
+        .to_string(),
     }
   }
 }
@@ -1,5 +1,5 @@
 mod attribute;
 mod field;
 
-pub use attribute::YaSerdeAttribute;
+pub use attribute::{NamespaceUri, YaSerdeAttribute};
 pub use field::{Field, YaSerdeField};
@@ -1,6 +1,8 @@
 use crate::common::{Field, YaSerdeAttribute, YaSerdeField};
-use proc_macro2::{Span, TokenStream};
+use crate::ser::label::build_label_name;
+use proc_macro2::TokenStream;
 use quote::quote;
+use std::collections::BTreeSet;
 use syn::{DataEnum, Fields, Generics, Ident};
 
 pub fn parse(
@@ -10,35 +12,141 @@ pub fn parse(
   root_attributes: &YaSerdeAttribute,
   generics: &Generics,
 ) -> TokenStream {
+  root_attributes.check_variant_aliases(data_enum);
+
+  if root_attributes.is_value_enum(data_enum) {
+    return parse_value_enum(data_enum, name, root_attributes, generics);
+  }
+  if root_attributes.untagged {
+    return parse_untagged(data_enum, name, root_attributes, generics);
+  }
+  if root_attributes.is_adjacently_tagged() {
+    return parse_adjacently_tagged(data_enum, name, root_attributes, generics);
+  }
+
   let namespaces_matching = root_attributes.get_namespace_matching(
-    &None,
+    &[],
     quote!(enum_namespace),
     quote!(named_element),
     true,
   );
 
+  let tagged = root_attributes.tag.is_some();
+
   let match_to_enum: TokenStream = data_enum
     .variants
     .iter()
-    .filter_map(|variant| parse_variant(variant, name))
+    .filter(|variant| {
+      matches!(variant.fields, Fields::Unit) || !YaSerdeAttribute::from(&variant.attrs).other
+    })
+    .filter_map(|variant| {
+      let pattern = variant_element_pattern(variant, root_attributes, tagged);
+      parse_variant(variant, name, pattern)
+    })
+    .collect();
+
+  // Unit variants are written as text with their prefix, which is resolved in the document
+  let match_text_to_enum: TokenStream = data_enum
+    .variants
+    .iter()
+    .filter(|variant| {
+      matches!(variant.fields, Fields::Unit) || !YaSerdeAttribute::from(&variant.attrs).other
+    })
+    .filter_map(|variant| {
+      let label = &variant.ident;
+      let xml_element_name = root_attributes
+        .variant_attributes(variant)
+        .xml_element_name(label);
+      let pattern = variant_value_pattern(variant, root_attributes, &xml_element_name);
+
+      match variant.fields {
+        Fields::Unit => Some(quote! {
+          #pattern => {
+            enum_value = ::std::option::Option::Some(#name::#label);
+            break;
+          }
+        }),
+        _ => parse_variant(variant, name, pattern),
+      }
+    })
     .collect();
+  let (unknown_element, unknown_text) = match YaSerdeAttribute::other_variant(data_enum) {
+    Some((label, keeps_value)) => {
+      let other_value = |value: TokenStream| {
+        if keeps_value {
+          quote!(#name::#label(#value.to_string()))
+        } else {
+          quote!(#name::#label)
+        }
+      };
+      let element_value = other_value(quote!(_named_element));
+      let text_value = other_value(quote!(content));
+
+      // A kept value is the text of the element, or an unknown child element as written
+      let unknown_element = if keeps_value && !tagged {
+        quote! {
+          (_, _named_element) if reader.depth() > start_depth => {
+            enum_value = ::std::option::Option::Some(#name::#label(reader.read_element_to_string()?));
+            let _end = reader.next_event();
+          }
+        }
+      } else {
+        quote! {
+          (_, _named_element) if #tagged || reader.depth() > start_depth => {
+            enum_value = ::std::option::Option::Some(#element_value);
+            if #tagged {
+              break;
+            }
+            let _start = reader.next_event();
+            reader.skip_element(|_event| {})?;
+          }
+        }
+      };
+
+      (
+        unknown_element,
+        quote! {
+          _ => {
+            enum_value = ::std::option::Option::Some(#text_value);
+            break;
+          }
+        },
+      )
+    }
+    None => (quote!(), quote!(_ => {})),
+  };
 
   let flatten = root_attributes.flatten;
   let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+  // Elements are matched on their namespace and local name, a tag value only on the value
   let element_name = if let Some(tag) = &root_attributes.tag {
+    let tag_attribute = root_attributes.tag_attribute_pattern();
     quote! {
-      attributes
-        .iter()
-        .find(|attr| attr.name.local_name.as_str() == #tag)
-        .ok_or(format!("Expected enum tagged with {}, found {:?}", #tag, event))?
-        .value.as_str()
+      (
+        ::std::option::Option::None::<&str>,
+        attributes
+          .iter()
+          .find(|attr| {
+            matches!(
+              (
+                reader.__resolve_namespace(&attr.name, undeclared_prefixes).as_deref(),
+                attr.name.local_name.as_str(),
+              ),
+              #tag_attribute
+            )
+          })
+          .ok_or(format!("Expected enum tagged with {}, found {:?}", #tag, event))?
+          .value.as_str(),
+      )
     }
   } else {
     quote! {
-      name.local_name.as_str()
+      (name.namespace.as_deref(), name.local_name.as_str())
     }
   };
+  let qualify_value = qualify_value(quote!(content.as_str()));
+  let undeclared_prefixes = undeclared_prefixes(root_attributes);
 
   quote! {
     impl #impl_generics ::yaserde::YaDeserialize for #name #ty_generics #where_clause {
@@ -46,9 +154,10 @@ pub fn parse(
       fn deserialize<R: ::std::io::Read>(
         reader: &mut ::yaserde::de::Deserializer<R>,
       ) -> ::std::result::Result<Self, ::std::string::String> {
+        let undeclared_prefixes: &[&str] = #undeclared_prefixes;
         let (named_element, enum_namespace) =
           if let ::yaserde::__xml::reader::XmlEvent::StartElement{ name, .. } = reader.peek()?.to_owned() {
-            (name.local_name.to_owned(), name.namespace.clone())
+            (name.local_name.to_owned(), reader.__resolve_namespace(&name, undeclared_prefixes))
           } else {
             (::std::string::String::from(#root), ::std::option::Option::None)
           };
@@ -56,7 +165,10 @@ pub fn parse(
         let start_depth = reader.depth();
         ::yaserde::__derive_debug!("Enum {} @ {}: start to parse {:?}", stringify!(#name), start_depth, named_element);
 
-        #namespaces_matching
+        // The namespace of a child element is checked by its parent
+        if reader.depth() == 0 && !#flatten {
+          #namespaces_matching
+        }
 
         #[allow(unused_assignments, unused_mut)]
         let mut enum_value = ::std::option::Option::None;
@@ -65,18 +177,23 @@ pub fn parse(
           let event = reader.peek()?.to_owned();
           ::yaserde::__derive_trace!("Enum {} @ {}: matching {:?}", stringify!(#name), start_depth, event);
           match event {
-            ::yaserde::__xml::reader::XmlEvent::StartElement { ref name, ref attributes, .. } => {
+            ::yaserde::__xml::reader::XmlEvent::StartElement {
+              ref name,
+              ref attributes,
+              namespace: ref element_namespaces,
+            } => {
               match #element_name {
                 #match_to_enum
+                #unknown_element
                 _named_element => {
                   let _root = reader.next_event();
                 }
               }
 
               if let ::yaserde::__xml::reader::XmlEvent::Characters(content) = reader.peek()?.to_owned() {
-                match content.as_str() {
-                  #match_to_enum
-                  _ => {}
+                match #qualify_value {
+                  #match_text_to_enum
+                  #unknown_text
                 }
               }
             }
@@ -105,15 +222,584 @@ pub fn parse(
         }
 
         ::yaserde::__derive_debug!("Enum {} @ {}: success", stringify!(#name), start_depth);
-        ::std::result::Result::Ok(enum_value.unwrap_or(<#name as ::std::default::Default>::default()))
+        enum_value.ok_or_else(|| {
+          ::std::format!("No variant of enum {} matched in {:?}", stringify!(#name), named_element)
+        })
+      }
+    }
+  }
+}
+
+fn parse_value_enum(
+  data_enum: &DataEnum,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+  generics: &Generics,
+) -> TokenStream {
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let unit_variants: Vec<_> = data_enum
+    .variants
+    .iter()
+    .filter(|variant| matches!(variant.fields, Fields::Unit))
+    .collect();
+  let element_names: Vec<String> = unit_variants
+    .iter()
+    .map(|variant| {
+      let variant_attrs = root_attributes.variant_attributes(variant);
+      build_label_name(
+        &variant.ident,
+        &variant_attrs,
+        &root_attributes.default_namespace,
+      )
+    })
+    .collect();
+  let labels = unit_variants.iter().map(|variant| &variant.ident);
+  let expected = element_names.join(", ");
+  let patterns = unit_variants
+    .iter()
+    .zip(&element_names)
+    .map(|(variant, element_name)| variant_value_pattern(variant, root_attributes, element_name));
+
+  let unknown_value = match YaSerdeAttribute::other_variant(data_enum) {
+    Some((label, true)) => quote!(::std::result::Result::Ok(#name::#label(value.to_string()))),
+    Some((label, false)) => quote!(::std::result::Result::Ok(#name::#label)),
+    None => quote! {
+      ::std::result::Result::Err(::std::format!(
+        "Unknown value {:?} for enum {}, expected one of: {}",
+        value,
+        stringify!(#name),
+        #expected,
+      ))
+    },
+  };
+
+  // `qualified_value` is the namespace, local name and value, see `qualify_value`
+  let match_value = quote! {
+    match qualified_value {
+      #(#patterns => ::std::result::Result::Ok(#name::#labels),)*
+      (_, _, value) => #unknown_value,
+    }
+  };
+  let qualify_value = qualify_value(quote!(value));
+
+  quote! {
+    impl #impl_generics ::yaserde::YaDeserialize for #name #ty_generics #where_clause {
+      fn deserialize<R: ::std::io::Read>(
+        reader: &mut ::yaserde::de::Deserializer<R>,
+      ) -> ::std::result::Result<Self, ::std::string::String> {
+        let start_depth = reader.depth();
+        ::yaserde::__derive_debug!("Enum {} @ {}: start to parse value", stringify!(#name), start_depth);
+
+        let element_namespaces = match reader.next_event()? {
+          ::yaserde::__xml::reader::XmlEvent::StartElement { namespace, .. } => namespace,
+          _ => return ::std::result::Result::Err("Start element not found".to_string()),
+        };
+
+        let mut value = ::std::string::String::new();
+        loop {
+          match reader.peek()?.to_owned() {
+            ::yaserde::__xml::reader::XmlEvent::Characters(content) => {
+              value = content;
+              let _event = reader.next_event()?;
+            }
+            ::yaserde::__xml::reader::XmlEvent::StartElement { .. } => {
+              let _start = reader.next_event()?;
+              reader.skip_element(|_event| {})?;
+            }
+            ::yaserde::__xml::reader::XmlEvent::EndElement { .. }
+              if reader.depth() == start_depth + 1 =>
+            {
+              break;
+            }
+            ::yaserde::__xml::reader::XmlEvent::EndDocument => {
+              return ::std::result::Result::Err(
+                ::std::format!("End of document, missing some content ?"),
+              );
+            }
+            _ => {
+              let _event = reader.next_event()?;
+            }
+          }
+        }
+
+        let value = value.as_str();
+        let qualified_value = #qualify_value;
+        #match_value
+      }
+
+      fn deserialize_value(
+        value: &str,
+      ) -> ::std::option::Option<::std::result::Result<Self, ::std::string::String>> {
+        let qualified_value = (::std::option::Option::None::<&str>, value, value);
+        ::std::option::Option::Some(#match_value)
       }
     }
   }
 }
 
-fn parse_variant(variant: &syn::Variant, name: &Ident) -> Option<TokenStream> {
-  let xml_element_name = YaSerdeAttribute::from(&variant.attrs).xml_element_name(&variant.ident);
+fn parse_untagged(
+  data_enum: &DataEnum,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+  generics: &Generics,
+) -> TokenStream {
+  if let Some(errors) = YaSerdeAttribute::check_single_value_variants(data_enum, "untagged") {
+    return errors;
+  }
+  YaSerdeAttribute::check_untagged_other(data_enum);
+
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+  let try_variants: TokenStream = data_enum
+    .variants
+    .iter()
+    .map(|variant| {
+      let label = &variant.ident;
+      let label_str = label.to_string();
+      let variant_attrs = root_attributes.variant_attributes(variant);
+      let xml_element_name = variant_attrs.xml_element_name(label);
+      let aliases = variant_attrs.alias_names();
+
+      let from_text = |simple_type: Field, action: TokenStream| {
+        let field_type = TokenStream::from(simple_type);
+        quote! {
+          #field_type::from_str(&text).map(#action).map_err(|e| e.to_string())
+        }
+      };
+
+      let attempt = match variant.fields {
+        Fields::Unit => quote! {
+          if matches!(text.clone()?.as_str(), #xml_element_name #(| #aliases)*) {
+            ::std::result::Result::Ok(#name::#label)
+          } else {
+            ::std::result::Result::Err(::std::format!("expected {:?}", #xml_element_name))
+          }
+        },
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+          let field = YaSerdeField::new(fields.unnamed[0].clone());
+
+          match field.get_type() {
+            Field::FieldStruct { struct_name } => quote! {
+              ::yaserde::de::from_str_with_config::<#struct_name>(&content, &reader.config())
+                .map(#name::#label)
+            },
+            Field::FieldOption { data_type } => match *data_type {
+              Field::FieldStruct { struct_name } => quote! {
+                ::yaserde::de::from_str_with_config::<#struct_name>(&content, &reader.config())
+                  .map(|value| #name::#label(::std::option::Option::Some(value)))
+              },
+              Field::FieldOption { .. } | Field::FieldVec { .. } => {
+                unreachable!("nested Option or Vec are rejected")
+              }
+              simple_type => {
+                let parse = from_text(
+                  simple_type,
+                  quote!(|value| #name::#label(::std::option::Option::Some(value))),
+                );
+                quote! {
+                  let text = text.clone()?;
+                  if text.is_empty() {
+                    ::std::result::Result::Ok(#name::#label(::std::option::Option::None))
+                  } else {
+                    #parse
+                  }
+                }
+              }
+            },
+            Field::FieldVec { .. } => unreachable!("Vec variants are rejected"),
+            simple_type => {
+              let parse = from_text(simple_type, quote!(#name::#label));
+              quote! {
+                let text = text.clone()?;
+                #parse
+              }
+            }
+          }
+        }
+        Fields::Unnamed(_) | Fields::Named(_) => {
+          unreachable!("variants with several or named fields are rejected")
+        }
+      };
+
+      quote! {
+        let attempt = (|| -> ::std::result::Result<Self, ::std::string::String> { #attempt })();
+        match attempt {
+          ::std::result::Result::Ok(value) => {
+            ::yaserde::__derive_debug!("Enum {}: matched variant {}", stringify!(#name), #label_str);
+            return ::std::result::Result::Ok(value);
+          }
+          ::std::result::Result::Err(msg) => errors.push(::std::format!("{}: {}", #label_str, msg)),
+        }
+      }
+    })
+    .collect();
 
+  quote! {
+    impl #impl_generics ::yaserde::YaDeserialize for #name #ty_generics #where_clause {
+      #[allow(unused_variables)]
+      fn deserialize<R: ::std::io::Read>(
+        reader: &mut ::yaserde::de::Deserializer<R>,
+      ) -> ::std::result::Result<Self, ::std::string::String> {
+        ::yaserde::__derive_debug!("Enum {} @ {}: start to parse untagged", stringify!(#name), reader.depth());
+
+        let content = reader.read_element_to_string()?;
+        let text =
+          ::yaserde::de::Deserializer::new_from_reader(content.as_bytes()).read_inner_text();
+
+        let mut errors: ::std::vec::Vec<::std::string::String> = ::std::vec![];
+
+        #try_variants
+
+        ::std::result::Result::Err(::std::format!(
+          "no variant of untagged enum {} matched: {}",
+          stringify!(#name),
+          errors.join("; "),
+        ))
+      }
+    }
+  }
+}
+
+fn parse_adjacently_tagged(
+  data_enum: &DataEnum,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+  generics: &Generics,
+) -> TokenStream {
+  if let Some(errors) =
+    YaSerdeAttribute::check_single_value_variants(data_enum, "adjacently tagged")
+  {
+    return errors;
+  }
+
+  let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+  let tag = root_attributes.tag.clone().unwrap_or_default();
+  let tag_is_element = root_attributes.is_tag_element();
+  let content_name = root_attributes.content.clone().unwrap_or_default();
+
+  // The tag attribute, and the tag and content elements, are matched on their namespace and
+  // local name
+  let tag_attribute = if tag_is_element {
+    quote!((_, _))
+  } else {
+    root_attributes.tag_attribute_pattern()
+  };
+  let tag_element = if tag_is_element {
+    let (namespace, local_name) = root_attributes.child_namespace_and_name(&tag);
+    quote!((#namespace, #local_name))
+  } else {
+    quote!((_, _))
+  };
+  let undeclared_prefixes = undeclared_prefixes(root_attributes);
+  let content_element = match &root_attributes.content {
+    Some(content) => {
+      let (namespace, local_name) = root_attributes.child_namespace_and_name(content);
+      quote!((#namespace, #local_name))
+    }
+    None => quote!((_, _)),
+  };
+
+  let match_variants: TokenStream = data_enum
+    .variants
+    .iter()
+    .filter(|variant| {
+      matches!(variant.fields, Fields::Unit) || !YaSerdeAttribute::from(&variant.attrs).other
+    })
+    .map(|variant| {
+      let label = &variant.ident;
+      let variant_attrs = root_attributes.variant_attributes(variant);
+      let xml_element_name = variant_attrs.xml_element_name(label);
+      let aliases = variant_attrs.alias_names();
+
+      if !matches!(variant.fields, Fields::Unit) && root_attributes.content.is_none() {
+        panic!(
+          "YaSerDe derive error: variant {} of an enum tagged by element requires content",
+          label
+        );
+      }
+
+      let from_content = |data_type: Field, action: TokenStream| match data_type {
+        Field::FieldStruct { struct_name } => quote! {
+          ::yaserde::de::from_str_with_config::<#struct_name>(&content, &reader.config())
+            .map(#action)
+        },
+        Field::FieldOption { .. } | Field::FieldVec { .. } => {
+          unreachable!("nested Option or Vec are rejected")
+        }
+        simple_type => {
+          let field_type = TokenStream::from(simple_type);
+          quote! {
+            let text =
+              ::yaserde::de::Deserializer::new_from_reader(content.as_bytes()).read_inner_text()?;
+            #field_type::from_str(&text).map(#action).map_err(|e| e.to_string())
+          }
+        }
+      };
+      let missing_content = quote! {
+        ::std::format!("Missing {} for variant {}", #content_name, #xml_element_name)
+      };
+
+      let build = match variant.fields {
+        Fields::Unit => quote!(::std::result::Result::Ok(#name::#label)),
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+          let field = YaSerdeField::new(fields.unnamed[0].clone());
+
+          match field.get_type() {
+            Field::FieldOption { data_type } => {
+              let parse = from_content(
+                *data_type,
+                quote!(|value| #name::#label(::std::option::Option::Some(value))),
+              );
+              quote! {
+                if let ::std::option::Option::Some(content) = content {
+                  #parse
+                } else {
+                  ::std::result::Result::Ok(#name::#label(::std::option::Option::None))
+                }
+              }
+            }
+            Field::FieldVec { .. } => unreachable!("Vec variants are rejected"),
+            data_type => {
+              let parse = from_content(data_type, quote!(#name::#label));
+              quote! {
+                let content = content.ok_or_else(|| #missing_content)?;
+                #parse
+              }
+            }
+          }
+        }
+        Fields::Unnamed(_) | Fields::Named(_) => {
+          unreachable!("variants with several or named fields are rejected")
+        }
+      };
+
+      quote! {
+        #xml_element_name #(| #aliases)* => {
+          #build
+        }
+      }
+    })
+    .collect();
+
+  let unknown_variant = match YaSerdeAttribute::other_variant(data_enum) {
+    Some((label, true)) => quote!(::std::result::Result::Ok(#name::#label(tag_value.to_string()))),
+    Some((label, false)) => quote!(::std::result::Result::Ok(#name::#label)),
+    None => quote! {
+      ::std::result::Result::Err(::std::format!(
+        "Unknown variant {:?} for enum {}",
+        tag_value,
+        stringify!(#name),
+      ))
+    },
+  };
+
+  quote! {
+    impl #impl_generics ::yaserde::YaDeserialize for #name #ty_generics #where_clause {
+      #[allow(unused_variables, unused_mut)]
+      fn deserialize<R: ::std::io::Read>(
+        reader: &mut ::yaserde::de::Deserializer<R>,
+      ) -> ::std::result::Result<Self, ::std::string::String> {
+        let undeclared_prefixes: &[&str] = #undeclared_prefixes;
+        let start_depth = reader.depth();
+        ::yaserde::__derive_debug!("Enum {} @ {}: start to parse adjacently tagged", stringify!(#name), start_depth);
+
+        let mut tag_value = ::std::option::Option::None;
+        let mut content: ::std::option::Option<::std::string::String> = ::std::option::Option::None;
+
+        if let ::yaserde::__xml::reader::XmlEvent::StartElement { attributes, .. } = reader.next_event()? {
+          if !#tag_is_element {
+            tag_value = attributes
+              .into_iter()
+              .find(|attr| {
+                matches!(
+                  (
+                    reader.__resolve_namespace(&attr.name, undeclared_prefixes).as_deref(),
+                    attr.name.local_name.as_str(),
+                  ),
+                  #tag_attribute
+                )
+              })
+              .map(|attr| attr.value);
+          }
+        } else {
+          return ::std::result::Result::Err("Start element not found".to_string());
+        }
+
+        loop {
+          match reader.peek()?.to_owned() {
+            ::yaserde::__xml::reader::XmlEvent::StartElement { ref name, .. }
+              if #tag_is_element && matches!(
+                (
+                  reader
+                    .__resolve_namespace(name, undeclared_prefixes)
+                    .unwrap_or_default()
+                    .as_str(),
+                  name.local_name.as_str(),
+                ),
+                #tag_element
+              ) =>
+            {
+              tag_value = ::std::option::Option::Some(reader.read_inner_text()?);
+            }
+            ::yaserde::__xml::reader::XmlEvent::StartElement { ref name, .. }
+              if matches!(
+                (
+                  reader
+                    .__resolve_namespace(name, undeclared_prefixes)
+                    .unwrap_or_default()
+                    .as_str(),
+                  name.local_name.as_str(),
+                ),
+                #content_element
+              ) =>
+            {
+              content = ::std::option::Option::Some(reader.read_element_to_string()?);
+              let _end = reader.next_event()?;
+            }
+            ::yaserde::__xml::reader::XmlEvent::StartElement { .. } => {
+              let _start = reader.next_event()?;
+              reader.skip_element(|_event| {})?;
+            }
+            ::yaserde::__xml::reader::XmlEvent::EndElement { .. }
+              if reader.depth() == start_depth + 1 =>
+            {
+              break;
+            }
+            ::yaserde::__xml::reader::XmlEvent::EndDocument => {
+              return ::std::result::Result::Err(
+                ::std::format!("End of document, missing some content ?"),
+              );
+            }
+            _ => {
+              let _event = reader.next_event()?;
+            }
+          }
+        }
+
+        let tag_value = tag_value.ok_or_else(|| ::std::format!("Expected enum tagged with {}", #tag))?;
+        ::yaserde::__derive_debug!("Enum {} @ {}: tagged {:?}", stringify!(#name), start_depth, tag_value);
+
+        match tag_value.as_str() {
+          #match_variants
+          tag_value => #unknown_variant,
+        }
+      }
+    }
+  }
+}
+
+/// Prefixes used by the enum element, tag and content without declaring them, which are resolved
+/// when reading, see `NamespaceUri::undeclared`
+fn undeclared_prefixes(root_attributes: &YaSerdeAttribute) -> TokenStream {
+  let tag_namespace = match &root_attributes.tag {
+    Some(tag) if root_attributes.is_tag_element() => {
+      Some(root_attributes.child_namespace_and_name(tag).0)
+    }
+    Some(_) => root_attributes.tag_attribute_name().0,
+    None => None,
+  };
+  let content_namespace = root_attributes
+    .content
+    .as_ref()
+    .map(|content| root_attributes.child_namespace_and_name(content).0);
+
+  let prefixes: BTreeSet<String> = std::iter::once(root_attributes.container_namespace_uri())
+    .chain(tag_namespace)
+    .chain(content_namespace)
+    .filter_map(|namespace| namespace.undeclared_prefix().map(str::to_string))
+    .collect();
+
+  quote!(&[#(#prefixes),*])
+}
+
+/// Prefixes and local names of a variant and of its aliases, an alias without prefix has the
+/// prefix of the variant
+fn variant_prefixes_and_names(
+  variant: &syn::Variant,
+  root_attributes: &YaSerdeAttribute,
+) -> Vec<(Option<String>, String)> {
+  let variant_attrs = root_attributes.variant_attributes(variant);
+  let xml_element_name = variant_attrs.xml_element_name(&variant.ident);
+
+  std::iter::once((variant_attrs.prefix.clone(), xml_element_name))
+    .chain(
+      variant_attrs
+        .alias
+        .iter()
+        .map(|alias| match alias.split_once(':') {
+          Some((prefix, name)) => (Some(prefix.to_string()), name.to_string()),
+          None => (variant_attrs.prefix.clone(), alias.clone()),
+        }),
+    )
+    .collect()
+}
+
+/// Pattern matching the `(namespace, local name)` of an element naming the variant, in the
+/// namespace of its prefix, or in any namespace when it has no declared prefix.
+///
+/// A `tag` value has no namespace, it is only matched on the local name.
+fn variant_element_pattern(
+  variant: &syn::Variant,
+  root_attributes: &YaSerdeAttribute,
+  tagged: bool,
+) -> TokenStream {
+  let patterns = variant_prefixes_and_names(variant, root_attributes)
+    .into_iter()
+    .map(|(prefix, name)| {
+      match prefix
+        .filter(|_| !tagged)
+        .and_then(|prefix| root_attributes.namespace_uri(&prefix))
+      {
+        Some(namespace) => quote!((::std::option::Option::Some(#namespace), #name)),
+        None => quote!((_, #name)),
+      }
+    });
+
+  quote!(#(#patterns)|*)
+}
+
+/// Pattern matching a `(namespace, local name, value)` text value naming the variant, see
+/// `qualify_value`: a prefixed value is matched on the namespace of its prefix, and a value
+/// whose prefix is not declared in the document is matched on `name` and the aliases.
+fn variant_value_pattern(
+  variant: &syn::Variant,
+  root_attributes: &YaSerdeAttribute,
+  name: &str,
+) -> TokenStream {
+  let qualified = variant_prefixes_and_names(variant, root_attributes)
+    .into_iter()
+    .filter_map(|(prefix, name)| {
+      let namespace = root_attributes.namespace_uri(&prefix?)?;
+      Some(quote!((::std::option::Option::Some(#namespace), #name, _)))
+    });
+  let names = std::iter::once(name.to_string())
+    .chain(root_attributes.variant_attributes(variant).alias_names());
+
+  quote!(#(#qualified |)* (::std::option::Option::None, _, #(#names)|*))
+}
+
+/// Split a text value into its namespace, resolved from its prefix in the namespaces of the
+/// current element, and its local name. A value without a declared prefix has no namespace.
+fn qualify_value(value: TokenStream) -> TokenStream {
+  quote! {
+    match #value.split_once(':') {
+      ::std::option::Option::Some((prefix, local_name)) => match element_namespaces.get(prefix) {
+        ::std::option::Option::Some(namespace) => {
+          (::std::option::Option::Some(namespace), local_name, #value)
+        }
+        ::std::option::Option::None => (::std::option::Option::None, #value, #value),
+      },
+      ::std::option::Option::None => (::std::option::Option::None, #value, #value),
+    }
+  }
+}
+
+fn parse_variant(
+  variant: &syn::Variant,
+  name: &Ident,
+  pattern: TokenStream,
+) -> Option<TokenStream> {
   let variant_name = {
     let label = &variant.ident;
     quote! { #name::#label }
@@ -121,7 +807,7 @@ fn parse_variant(variant: &syn::Variant, name: &Ident) -> Option<TokenStream> {
 
   match variant.fields {
     Fields::Unit => Some(quote! {
-      #xml_element_name => {
+      #pattern => {
         enum_value = ::std::option::Option::Some(#variant_name);
         break;
       }
@@ -141,7 +827,7 @@ fn parse_variant(variant: &syn::Variant, name: &Ident) -> Option<TokenStream> {
           .take(1)
           .map(|_field| {
             quote! {
-              #xml_element_name => {
+              #pattern => {
                 #field_visitors
                 #call_visitors
               }
@@ -191,25 +877,9 @@ fn build_unnamed_field_visitors(fields: &syn::FieldsUnnamed) -> TokenStream {
         )
       };
 
+      // Structs are read by their own deserializer
       match field.get_type() {
-        Field::FieldStruct { struct_name } => {
-          let struct_id: String = struct_name
-            .segments
-            .iter()
-            .map(|s| s.ident.to_string())
-            .collect();
-
-          Some(make_visitor(
-            &Ident::new("visit_str", Span::call_site()),
-            &quote! { #struct_name },
-            &quote! {
-              let content = "<".to_string() + #struct_id + ">" + v + "</" + #struct_id + ">";
-              let value: ::std::result::Result<#struct_name, ::std::string::String> =
-                ::yaserde::de::from_str(&content);
-              value
-            },
-          ))
-        }
+        Field::FieldStruct { .. } => None,
         Field::FieldOption { data_type } | Field::FieldVec { data_type } => match *data_type {
           Field::FieldStruct { .. } => None,
           simple_type => Some(simple_type_visitor(simple_type)),
@@ -1,19 +1,22 @@
 use super::build_default_value::{build_default_value, build_default_vec_value};
-use crate::common::{Field, YaSerdeAttribute, YaSerdeField};
+use crate::common::{Field, NamespaceUri, YaSerdeAttribute, YaSerdeField};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use std::collections::{BTreeMap, BTreeSet};
 use syn::{DataStruct, Generics, Ident};
 
 pub fn parse(
   data_struct: &DataStruct,
   name: &Ident,
-  root_namespace: &str,
+  root_namespace: &NamespaceUri,
   root: &str,
   root_attributes: &YaSerdeAttribute,
   generics: &Generics,
 ) -> TokenStream {
+  check_field_aliases(data_struct, root_attributes);
+
   let namespaces_matching = root_attributes.get_namespace_matching(
-    &None,
+    &[],
     quote!(struct_namespace),
     quote!(named_element),
     true,
@@ -22,8 +25,10 @@ pub fn parse(
   let variables: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
     .filter_map(|field| match field.get_type() {
+      _ if field.get_deserialize_with().is_some() => build_default_value(&field, None),
       Field::FieldStruct { struct_name } => build_default_value(&field, Some(quote!(#struct_name))),
       Field::FieldOption { .. } => build_default_value(&field, None),
       Field::FieldVec { data_type } => match *data_type {
@@ -50,7 +55,9 @@ pub fn parse(
   let field_visitors: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
+    .filter(|field| field.get_deserialize_with().is_none())
     .filter(|field| {
       if field.is_attribute() {
         return true;
@@ -77,7 +84,9 @@ pub fn parse(
 
         Some(quote! {
           #[allow(non_snake_case, non_camel_case_types)]
-          struct #visitor_label;
+          struct #visitor_label {
+            config: ::yaserde::de::Config,
+          }
           impl<'de> ::yaserde::Visitor<'de> for #visitor_label {
             type Value = #struct_name;
 
@@ -85,8 +94,14 @@ pub fn parse(
               self,
               v: &str,
             ) -> ::std::result::Result<Self::Value, ::std::string::String> {
+              if let ::std::option::Option::Some(value) =
+                <#struct_name as ::yaserde::YaDeserialize>::deserialize_value(v)
+              {
+                return value;
+              }
+
               let content = format!("{}{}{}", #xml_opening, v, #xml_closing);
-              ::yaserde::de::from_str(&content)
+              ::yaserde::de::from_str_with_config(&content, &self.config)
             }
           }
         })
@@ -127,6 +142,13 @@ pub fn parse(
         Field::FieldStruct { struct_name } => struct_visitor(struct_name),
         Field::FieldOption { data_type } => match *data_type {
           Field::FieldStruct { struct_name } => struct_visitor(struct_name),
+          Field::FieldVec { data_type } if field.is_attribute() || field.is_list() => {
+            match *data_type {
+              Field::FieldStruct { struct_name } => struct_visitor(struct_name),
+              Field::FieldOption { .. } | Field::FieldVec { .. } => None,
+              simple_type => simple_type_visitor(simple_type),
+            }
+          }
           Field::FieldOption { .. } | Field::FieldVec { .. } => None,
           simple_type => simple_type_visitor(simple_type),
         },
@@ -143,17 +165,27 @@ pub fn parse(
   let call_visitors: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
     .filter(|field| !field.is_attribute() && !field.is_flatten())
     .filter_map(|field| {
       let value_label = field.get_value_label();
-      let label_name = field.renamed_label_without_namespace();
+      let pattern = field.element_pattern(root_attributes);
 
-      let namespace = field.prefix_namespace(root_attributes);
+      if let Some(deserialize_with) = field.get_deserialize_with() {
+        return (!field.is_text_content()).then(|| {
+          quote! {
+            #pattern => {
+              let value = reader.read_inner_value(|reader| #deserialize_with(reader))?;
+              #value_label = ::std::option::Option::Some(value);
+            }
+          }
+        });
+      }
 
       let visit_struct = |struct_name: syn::Path, action: TokenStream| {
         Some(quote! {
-          (#namespace, #label_name) => {
+          #pattern => {
             if depth == 0 {
               // Don't count current struct's StartElement as substruct's StartElement
               let _root = reader.next_event();
@@ -187,14 +219,55 @@ pub fn parse(
         simple_type => visit_simple(simple_type, action),
       };
 
+      let visit_list = |data_type: &Field, action: TokenStream| {
+        let (visitor, new_visitor) = match data_type {
+          Field::FieldStruct { struct_name } => (
+            Ident::new("visit_str", field.get_span()),
+            new_struct_visitor(&field.get_visitor_ident(Some(struct_name))),
+          ),
+          Field::FieldOption { .. } | Field::FieldVec { .. } => {
+            unimplemented!("Option or Vec nested in list Vec<>")
+          }
+          simple_type => {
+            let visitor_label = field.get_visitor_ident(None);
+            (
+              simple_type.get_simple_type_visitor(),
+              quote!(#visitor_label {}),
+            )
+          }
+        };
+        let split_list = field.split_list(quote!(content));
+
+        Some(quote! {
+          #pattern => {
+            let content = reader.read_inner_text()?;
+
+            let values = #split_list
+              .map(|item| #new_visitor.#visitor(item))
+              .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?;
+            #value_label #action;
+          }
+        })
+      };
+
       match field.get_type() {
         Field::FieldStruct { struct_name } => {
           visit_struct(struct_name, quote! { = ::std::option::Option::Some(value) })
         }
-        Field::FieldOption { data_type } => {
-          visit_sub(data_type, quote! { = ::std::option::Option::Some(value) })
+        Field::FieldOption { data_type } => match *data_type {
+          Field::FieldVec { data_type } if field.is_list() => {
+            visit_list(&data_type, quote! { = ::std::option::Option::Some(values) })
+          }
+          data_type => visit_sub(
+            Box::new(data_type),
+            quote! { = ::std::option::Option::Some(value) },
+          ),
+        },
+        Field::FieldVec { data_type } if field.is_list() => {
+          visit_list(&data_type, quote! { = values })
         }
-        Field::FieldVec { data_type } => visit_sub(data_type, quote! { .push(value) }),
+        Field::FieldVec { data_type } => visit_sub(data_type, quote! { .push(value) })
+          .map(|item_visitor| build_wrapper_visitor(&field, name, root_attributes, item_visitor)),
         simple_type => visit_simple(simple_type, quote! { = ::std::option::Option::Some(value) }),
       }
     })
@@ -203,18 +276,19 @@ pub fn parse(
   let call_flatten_visitors: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
     .filter(|field| !field.is_attribute() && field.is_flatten())
     .map(|field| {
       let value_label = field.get_value_label();
 
       match field.get_type() {
         Field::FieldStruct { .. } => quote! {
-          #value_label = Some(::yaserde::de::from_str(&unused_xml_elements)?);
+          #value_label = Some(reader.read_flattened(&unused_xml_elements)?);
         },
         Field::FieldOption { data_type } => match *data_type {
           Field::FieldStruct { .. } => quote! {
-            #value_label = ::yaserde::de::from_str(&unused_xml_elements).ok();
+            #value_label = reader.read_flattened(&unused_xml_elements).ok();
           },
           field_type => unimplemented!(r#""flatten" is not implemented for {:?}"#, field_type),
         },
@@ -226,18 +300,30 @@ pub fn parse(
   let attributes_loading: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
     .filter(|field| field.is_attribute())
     .filter_map(|field| {
       let label = field.get_value_label();
-      let label_name = field.renamed_label_without_namespace();
+      let attribute_pattern = field.attribute_pattern(root_attributes);
+      let attribute_name = attribute_name(quote!(attr.name));
       let visitor_label = field.get_visitor_ident(None);
 
-      let visit = |action: &TokenStream, visitor: &Ident, visitor_label: &Ident| {
+      if let Some(deserialize_with) = field.get_deserialize_with() {
+        return Some(quote! {
+          for attr in attributes {
+            if matches!(#attribute_name, #attribute_pattern) {
+              #label = ::std::option::Option::Some(#deserialize_with(&attr.value)?);
+            }
+          }
+        });
+      }
+
+      let visit = |action: &TokenStream, visitor: &Ident, new_visitor: &TokenStream| {
         Some(quote! {
           for attr in attributes {
-            if attr.name.local_name == #label_name {
-              let visitor = #visitor_label{};
+            if matches!(#attribute_name, #attribute_pattern) {
+              let visitor = #new_visitor;
               let value = visitor.#visitor(&attr.value)?;
               #label #action;
             }
@@ -245,12 +331,14 @@ pub fn parse(
         })
       };
 
-      let visit_vec = |action: &TokenStream, visitor: &Ident, visitor_label: &Ident| {
+      let split_list = field.split_list(quote!(attr.value));
+
+      let visit_vec = |action: &TokenStream, visitor: &Ident, new_visitor: &TokenStream| {
         Some(quote! {
           for attr in attributes {
-            if attr.name.local_name == #label_name {
-              for value in attr.value.split_whitespace() {
-                let visitor = #visitor_label{};
+            if matches!(#attribute_name, #attribute_pattern) {
+              for value in #split_list {
+                let visitor = #new_visitor;
                 let value = visitor.#visitor(value)?;
                 #label #action;
               }
@@ -259,10 +347,25 @@ pub fn parse(
         })
       };
 
+      let visit_option_vec = |visitor: &Ident, new_visitor: &TokenStream| {
+        Some(quote! {
+          for attr in attributes {
+            if matches!(#attribute_name, #attribute_pattern) {
+              let mut values = ::std::vec::Vec::new();
+              for value in #split_list {
+                let visitor = #new_visitor;
+                values.push(visitor.#visitor(value)?);
+              }
+              #label = ::std::option::Option::Some(values);
+            }
+          }
+        })
+      };
+
       let visit_string = || {
         Some(quote! {
           for attr in attributes {
-            if attr.name.local_name == #label_name {
+            if matches!(#attribute_name, #attribute_pattern) {
               #label = Some(attr.value.to_owned());
             }
           }
@@ -273,7 +376,7 @@ pub fn parse(
         visit(
           &action,
           &Ident::new("visit_str", Span::call_site()),
-          &field.get_visitor_ident(Some(&struct_name)),
+          &new_struct_visitor(&field.get_visitor_ident(Some(&struct_name))),
         )
       };
 
@@ -281,7 +384,7 @@ pub fn parse(
         visit(
           &action,
           &simple_type.get_simple_type_visitor(),
-          &visitor_label,
+          &quote!(#visitor_label {}),
         )
       };
 
@@ -293,20 +396,34 @@ pub fn parse(
 
       match field.get_type() {
         Field::FieldString => visit_string(),
-        Field::FieldOption { data_type } => {
-          visit_sub(data_type, quote! { = ::std::option::Option::Some(value) })
-        }
+        Field::FieldOption { data_type } => match *data_type {
+          Field::FieldVec { data_type } => match data_type.as_ref() {
+            Field::FieldStruct { struct_name } => visit_option_vec(
+              &Ident::new("visit_str", field.get_span()),
+              &new_struct_visitor(&field.get_visitor_ident(Some(struct_name))),
+            ),
+            Field::FieldOption { .. } | Field::FieldVec { .. } => unimplemented!("Not supported"),
+            simple_type => visit_option_vec(
+              &simple_type.get_simple_type_visitor(),
+              &quote!(#visitor_label {}),
+            ),
+          },
+          data_type => visit_sub(
+            Box::new(data_type),
+            quote! { = ::std::option::Option::Some(value) },
+          ),
+        },
         Field::FieldVec { data_type } => match data_type.as_ref() {
           Field::FieldStruct { struct_name } => visit_vec(
             &quote! { .push(value) },
             &Ident::new("visit_str", field.get_span()),
-            &field.get_visitor_ident(Some(struct_name)),
+            &new_struct_visitor(&field.get_visitor_ident(Some(struct_name))),
           ),
           Field::FieldOption { .. } | Field::FieldVec { .. } => unimplemented!("Not supported"),
           simple_type => visit_vec(
             &quote! { .push(value) },
             &simple_type.get_simple_type_visitor(),
-            &visitor_label,
+            &quote!(#visitor_label {}),
           ),
         },
         Field::FieldStruct { struct_name } => visit_struct(struct_name, quote! { = Some(value) }),
@@ -318,7 +435,8 @@ pub fn parse(
   let set_text: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
     .filter_map(|field| {
       let label = field.get_value_label();
 
@@ -328,51 +446,161 @@ pub fn parse(
           .then_some(quote! { #label = #action; })
       };
 
+      if let Some(deserialize_with) = field.get_deserialize_with() {
+        return set_text(&quote! { Some(#deserialize_with(text_content)?) });
+      }
+
+      let deserialize_value = |struct_name: &syn::Path, value: TokenStream| {
+        quote! {
+          <#struct_name as ::yaserde::YaDeserialize>::deserialize_value(#value)
+            .unwrap_or_else(|| ::std::result::Result::Err(::std::format!(
+              "{} can't be read from a text content",
+              stringify!(#struct_name),
+            )))
+        }
+      };
+
       match field.get_type() {
         Field::FieldString => set_text(&quote! { Some(text_content.to_owned()) }),
         Field::FieldOption { data_type } => match *data_type {
           Field::FieldString => set_text(
             &quote! { if text_content.is_empty() { None } else { Some(text_content.to_owned()) }},
           ),
-          _ => None,
+          Field::FieldStruct { struct_name } => {
+            let value = deserialize_value(&struct_name, quote!(text_content));
+            set_text(&quote! {
+              if text_content.is_empty() {
+                None
+              } else {
+                Some(#value?)
+              }
+            })
+          }
+          Field::FieldOption { .. } | Field::FieldVec { .. } => None,
+          simple_type => {
+            let type_token = TokenStream::from(simple_type);
+            set_text(&quote! {
+              if text_content.is_empty() {
+                None
+              } else {
+                Some(#type_token::from_str(text_content).map_err(|e| e.to_string())?)
+              }
+            })
+          }
+        },
+        Field::FieldVec { data_type } => match *data_type {
+          Field::FieldStruct { struct_name } => {
+            let value = deserialize_value(&struct_name, quote!(item));
+            let split_list = field.split_list(quote!(text_content));
+
+            set_text(&quote! {
+              #split_list
+                .map(|item| #value)
+                .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?
+            })
+          }
+          Field::FieldOption { .. } | Field::FieldVec { .. } => None,
+          simple_type => {
+            let visitor = simple_type.get_simple_type_visitor();
+            let visitor_label = field.get_visitor_ident(None);
+            let split_list = field.split_list(quote!(text_content));
+
+            set_text(&quote! {
+              #split_list
+                .map(|item| #visitor_label{}.#visitor(item))
+                .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?
+            })
+          }
         },
-        Field::FieldStruct { .. } | Field::FieldVec { .. } => None,
+        Field::FieldStruct { struct_name } => {
+          let value = deserialize_value(&struct_name, quote!(text_content));
+          set_text(&quote! { Some(#value?) })
+        }
         simple_type => {
           let type_token = TokenStream::from(simple_type);
-          set_text(&quote! { #type_token::from_str(text_content).unwrap() })
+          set_text(
+            &quote! { Some(#type_token::from_str(text_content).map_err(|e| e.to_string())?) },
+          )
         }
       }
     })
     .collect();
 
+  let container_default = root_attributes.default_function(quote!(Self));
+  let init_default = container_default
+    .as_ref()
+    .map(|function| quote!(let yaserde_default: Self = #function();));
+
   let struct_builder: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
     .map(|field| {
       let label = &field.label();
       let value_label = field.get_value_label();
 
-      match field.get_type() {
-        Field::FieldOption { .. } | Field::FieldVec { .. } => {
-          quote! { #label: #value_label, }
+      // Missing values are taken from the field default, or from the container default
+      let field_default = field
+        .get_default_function()
+        .map(|function| quote!(#function()));
+      let container_default = container_default
+        .as_ref()
+        .map(|_| quote!(yaserde_default.#label));
+
+      // Skipped fields are never read, they always take their default value
+      if field.is_skip_deserializing() {
+        let default = field_default
+          .or(container_default)
+          .unwrap_or(quote!(::std::default::Default::default()));
+        return quote! { #label: #default, };
+      }
+
+      // Encoded byte buffers are read as a string, then decoded into the field type
+      let value_label = if field.is_binary() {
+        let decode = field.decode_binary(quote!(&value));
+        quote!(#value_label.map(|value| #decode).transpose()?)
+      } else {
+        quote!(#value_label)
+      };
+
+      // Values read by a `deserialize_with` function already have the field type
+      let read_with = field.get_deserialize_with().is_some();
+
+      let value = match field.get_type() {
+        _ if read_with && field.is_option() => quote!(#value_label),
+        Field::FieldOption { .. } if !read_with => quote!(#value_label),
+        Field::FieldVec { .. } if !read_with => {
+          return match field_default.or(container_default) {
+            Some(default) => quote! {
+              #label: if #value_label.is_empty() { #default } else { #value_label },
+            },
+            None => quote! { #label: #value_label, },
+          };
         }
         _ => {
-          if let Some(default_function) = field.get_default_function() {
-            quote! { #label: #value_label.unwrap_or_else(|| #default_function()), }
-          } else {
-            let error = format!(
-              "{} is a required field of {}",
-              label
-                .as_ref()
-                .map(|label| label.to_string())
-                .unwrap_or_default(),
-              name
-            );
-
-            quote! { #label: #value_label.ok_or_else(|| #error.to_string())?, }
-          }
+          return match (field_default, container_default) {
+            (Some(default), _) => quote! { #label: #value_label.unwrap_or_else(|| #default), },
+            (None, Some(default)) => quote! { #label: #value_label.unwrap_or(#default), },
+            (None, None) => {
+              let error = format!(
+                "{} is a required field of {}",
+                label
+                  .as_ref()
+                  .map(|label| label.to_string())
+                  .unwrap_or_default(),
+                name
+              );
+
+              quote! { #label: #value_label.ok_or_else(|| #error.to_string())?, }
+            }
+          };
         }
+      };
+
+      // Optional values keep `None` when missing, unless the container has a default
+      match container_default {
+        Some(default) => quote! { #label: #value.or(#default), },
+        None => quote! { #label: #value, },
       }
     })
     .collect();
@@ -383,6 +611,15 @@ pub fn parse(
     build_code_for_unused_xml_events(&call_flatten_visitors)
   };
 
+  let (deny_unknown_element, deny_unknown_attributes, deny_unknown_text) =
+    build_deny_unknown_fields(data_struct, name, root_attributes);
+
+  let flattened_acceptance = build_flattened_acceptance(data_struct, root_attributes);
+  let undeclared_prefixes = undeclared_prefixes(
+    &deserializable_fields(data_struct, root_attributes),
+    root_attributes,
+  );
+
   let flatten = root_attributes.flatten;
   let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
@@ -392,17 +629,22 @@ pub fn parse(
       fn deserialize<R: ::std::io::Read>(
         reader: &mut ::yaserde::de::Deserializer<R>,
       ) -> ::std::result::Result<Self, ::std::string::String> {
+        let undeclared_prefixes: &[&str] = #undeclared_prefixes;
         let (named_element, struct_namespace) =
           if let ::yaserde::__xml::reader::XmlEvent::StartElement { name, .. } = reader.peek()?.to_owned() {
-            (name.local_name.to_owned(), name.namespace.clone())
+            (name.local_name.to_owned(), reader.__resolve_namespace(&name, undeclared_prefixes))
           } else {
             (::std::string::String::from(#root), ::std::option::Option::None)
           };
         let start_depth = reader.depth();
         ::yaserde::__derive_debug!("Struct {} @ {}: start to parse {:?}", stringify!(#name), start_depth,
                named_element);
+        // The element of a flattening struct is checked by that struct
+        let flattened_root = start_depth == 0 && reader.flattened();
 
-        if reader.depth() == 0 {
+        // The namespace of a child element is checked by its parent, and a flattened struct
+        // reads the element of its parent
+        if reader.depth() == 0 && !#flatten {
           #namespaces_matching
         }
 
@@ -420,7 +662,9 @@ pub fn parse(
           );
           match event {
             ::yaserde::__xml::reader::XmlEvent::StartElement{ref name, ref attributes, ..} => {
-              let namespace = name.namespace.clone().unwrap_or_default();
+              let namespace = reader
+                .__resolve_namespace(name, undeclared_prefixes)
+                .unwrap_or_default();
               if depth == 0 && name.local_name == #root && namespace.as_str() == #root_namespace {
                 // Consume root element. We must do this first. In the case it shares a name with a child element, we don't
                 // want to prematurely match the child element below.
@@ -431,6 +675,7 @@ pub fn parse(
                 match (namespace.as_str(), name.local_name.as_str()) {
                   #call_visitors
                   _ => {
+                    #deny_unknown_element
                     let event = reader.next_event()?;
                     #write_unused
 
@@ -444,6 +689,7 @@ pub fn parse(
               }
               if depth == 0 { // Look for attributes only at element start
                 #attributes_loading
+                #deny_unknown_attributes
               }
               depth += 1;
             }
@@ -463,6 +709,7 @@ pub fn parse(
             }
             ::yaserde::__xml::reader::XmlEvent::Characters(ref text_content) => {
               #set_text
+              #deny_unknown_text
               let event = reader.next_event()?;
               #write_unused
             }
@@ -475,12 +722,380 @@ pub fn parse(
         #visit_unused
 
         ::yaserde::__derive_debug!("Struct {} @ {}: success", stringify!(#name), start_depth);
+        #init_default
         ::std::result::Result::Ok(#name{#struct_builder})
       }
+
+      #flattened_acceptance
+    }
+  }
+}
+
+/// Types of the flattened fields, which read what the struct leaves of its element
+fn flattened_types(fields: &[YaSerdeField]) -> Vec<syn::Path> {
+  fields
+    .iter()
+    .filter(|field| field.is_flatten() && !field.is_attribute())
+    .filter_map(|field| match field.get_type() {
+      Field::FieldStruct { struct_name } => Some(struct_name),
+      Field::FieldOption { data_type } => match *data_type {
+        Field::FieldStruct { struct_name } => Some(struct_name),
+        _ => None,
+      },
+      _ => None,
+    })
+    .collect()
+}
+
+fn deserializable_fields(
+  data_struct: &DataStruct,
+  root_attributes: &YaSerdeAttribute,
+) -> Vec<YaSerdeField> {
+  data_struct
+    .fields
+    .iter()
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_skip_deserializing())
+    .collect()
+}
+
+/// Prefixes used by the struct without declaring them, which are resolved when reading, see
+/// `NamespaceUri::undeclared`
+fn undeclared_prefixes(fields: &[YaSerdeField], root_attributes: &YaSerdeAttribute) -> TokenStream {
+  let root_namespace = root_attributes.container_namespace_uri();
+
+  let namespaces = fields.iter().flat_map(|field| {
+    if field.is_attribute() {
+      return field
+        .attribute_names(root_attributes)
+        .into_iter()
+        .filter_map(|(namespace, _name)| namespace)
+        .collect();
+    }
+
+    std::iter::once(field.prefix_namespace(root_attributes))
+      .chain(
+        field
+          .aliases(root_attributes)
+          .into_iter()
+          .map(|(namespace, _name)| namespace),
+      )
+      .chain(
+        field
+          .wrapper_namespace_and_name(root_attributes)
+          .map(|(namespace, _name)| namespace),
+      )
+      .collect::<Vec<_>>()
+  });
+
+  let prefixes: BTreeSet<String> = std::iter::once(root_namespace)
+    .chain(namespaces)
+    .filter_map(|namespace| namespace.undeclared_prefix().map(str::to_string))
+    .collect();
+
+  quote!(&[#(#prefixes),*])
+}
+
+/// Whether the `&OwnedName` `name` is the name of an attribute read by the struct or by its flattened fields
+fn accepts_attribute(
+  fields: &[YaSerdeField],
+  root_attributes: &YaSerdeAttribute,
+  name: TokenStream,
+) -> TokenStream {
+  let attribute_patterns: Vec<TokenStream> = fields
+    .iter()
+    .filter(|field| field.is_attribute())
+    .map(|field| field.attribute_pattern(root_attributes))
+    .collect();
+  let flattened = flattened_types(fields);
+
+  let attribute_name = attribute_name(name.clone());
+  let own_attributes = (!attribute_patterns.is_empty())
+    .then(|| quote!(matches!(#attribute_name, #(#attribute_patterns)|*)));
+  let flattened_attributes = flattened.iter().map(
+    |flattened| quote!(<#flattened as ::yaserde::YaDeserialize>::accepts_flattened_attribute(reader, #name)),
+  );
+  let conditions: Vec<TokenStream> = own_attributes
+    .into_iter()
+    .chain(flattened_attributes)
+    .collect();
+
+  if conditions.is_empty() {
+    quote!(false)
+  } else {
+    quote!(#(#conditions)||*)
+  }
+}
+
+/// Whether the `&OwnedName` `name` is the name of a child element read by the struct or by its
+/// flattened fields
+fn accepts_element(
+  fields: &[YaSerdeField],
+  root_attributes: &YaSerdeAttribute,
+  name: TokenStream,
+) -> TokenStream {
+  let element_patterns: Vec<TokenStream> = fields
+    .iter()
+    .filter(|field| !field.is_attribute() && !field.is_text_content() && !field.is_flatten())
+    .map(
+      |field| match field.wrapper_namespace_and_name(root_attributes) {
+        Some((namespace, name)) => quote!((#namespace, #name)),
+        None => field.element_pattern(root_attributes),
+      },
+    )
+    .collect();
+  let flattened = flattened_types(fields);
+
+  let own_elements = (!element_patterns.is_empty()).then(|| {
+    quote! {
+      matches!(
+        (
+          reader
+            .__resolve_namespace(#name, undeclared_prefixes)
+            .unwrap_or_default()
+            .as_str(),
+          #name.local_name.as_str(),
+        ),
+        #(#element_patterns)|*
+      )
+    }
+  });
+  let flattened_elements = flattened.iter().map(
+    |flattened| quote!(<#flattened as ::yaserde::YaDeserialize>::accepts_flattened_element(reader, #name)),
+  );
+  let conditions: Vec<TokenStream> = own_elements.into_iter().chain(flattened_elements).collect();
+
+  if conditions.is_empty() {
+    quote!(false)
+  } else {
+    quote!(#(#conditions)||*)
+  }
+}
+
+/// Whether the struct or its flattened fields read the text content
+fn accepts_text(fields: &[YaSerdeField]) -> TokenStream {
+  if fields.iter().any(|field| field.is_text_content()) {
+    return quote!(true);
+  }
+
+  let flattened = flattened_types(fields);
+  if flattened.is_empty() {
+    quote!(false)
+  } else {
+    quote!(#(<#flattened as ::yaserde::YaDeserialize>::accepts_flattened_text())||*)
+  }
+}
+
+/// Implementation of `YaDeserialize::accepts_flattened_attribute`,
+/// `YaDeserialize::accepts_flattened_element` and `YaDeserialize::accepts_flattened_text`, telling
+/// a struct flattening this one what it reads
+fn build_flattened_acceptance(
+  data_struct: &DataStruct,
+  root_attributes: &YaSerdeAttribute,
+) -> TokenStream {
+  let fields = deserializable_fields(data_struct, root_attributes);
+  let accepts_attribute = accepts_attribute(&fields, root_attributes, quote!(name));
+  let accepts_element = accepts_element(&fields, root_attributes, quote!(name));
+  let accepts_text = accepts_text(&fields);
+  let undeclared_prefixes = undeclared_prefixes(&fields, root_attributes);
+
+  quote! {
+    #[allow(unused_variables)]
+    fn accepts_flattened_attribute<R: ::std::io::Read>(
+      reader: &::yaserde::de::Deserializer<R>,
+      name: &::yaserde::__xml::name::OwnedName,
+    ) -> bool {
+      let undeclared_prefixes: &[&str] = #undeclared_prefixes;
+      #accepts_attribute
+    }
+
+    #[allow(unused_variables)]
+    fn accepts_flattened_element<R: ::std::io::Read>(
+      reader: &::yaserde::de::Deserializer<R>,
+      name: &::yaserde::__xml::name::OwnedName,
+    ) -> bool {
+      let undeclared_prefixes: &[&str] = #undeclared_prefixes;
+      #accepts_element
+    }
+
+    fn accepts_flattened_text() -> bool {
+      #accepts_text
+    }
+  }
+}
+
+/// Checks rejecting unexpected child elements, attributes and text content, enabled by the
+/// `deny_unknown_fields` attribute or by the deserializer configuration.
+///
+/// Child elements, attributes and text content are checked against the flattened fields too. The
+/// element read by a flattened struct is checked by the struct flattening it instead.
+fn build_deny_unknown_fields(
+  data_struct: &DataStruct,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+) -> (TokenStream, TokenStream, TokenStream) {
+  let fields = deserializable_fields(data_struct, root_attributes);
+  let flattened = flattened_types(&fields);
+
+  let struct_name = name.to_string();
+  let deny = root_attributes.deny_unknown_fields;
+  let expected = |names: Vec<String>, kind: &str| {
+    if names.is_empty() {
+      format!("no {} is expected", kind)
+    } else {
+      format!("expected one of: {}", names.join(", "))
+    }
+  };
+
+  let elements = fields
+    .iter()
+    .filter(|field| !field.is_attribute() && !field.is_text_content() && !field.is_flatten())
+    .flat_map(|field| field.accepted_names(root_attributes))
+    .chain(flattened.iter().map(|flattened| {
+      format!(
+        "an element of {}",
+        quote!(#flattened).to_string().replace(' ', "")
+      )
+    }))
+    .collect();
+  let expected_elements = expected(elements, "child element");
+  // Own fields are matched before, so only the flattened fields are left to accept the element
+  let accepts_element = (!flattened.is_empty()).then(|| {
+    quote!(&& !(#(<#flattened as ::yaserde::YaDeserialize>::accepts_flattened_element(reader, name))||*))
+  });
+
+  let deny_unknown_element = quote! {
+    if depth > 0 && (#deny || reader.deny_unknown_fields()) && !flattened_root #accepts_element {
+      return ::std::result::Result::Err(::std::format!(
+        "Unexpected element <{}> in {}, {}",
+        name.local_name, #struct_name, #expected_elements,
+      ));
+    }
+  };
+
+  let attributes = fields
+    .iter()
+    .filter(|field| field.is_attribute())
+    .flat_map(|field| field.accepted_names(root_attributes))
+    .chain(flattened.iter().map(|flattened| {
+      format!(
+        "an attribute of {}",
+        quote!(#flattened).to_string().replace(' ', "")
+      )
+    }))
+    .collect();
+  let expected_attributes = expected(attributes, "attribute");
+  let is_accepted = accepts_attribute(&fields, root_attributes, quote!(name));
+
+  let deny_unknown_attributes = quote! {
+    if (#deny || reader.deny_unknown_fields()) && !flattened_root {
+      if let ::std::option::Option::Some(attr) = attributes.iter().find(|attr| {
+        let name = &attr.name;
+        !(#is_accepted)
+      }) {
+        return ::std::result::Result::Err(::std::format!(
+          "Unexpected attribute {} in {}, {}",
+          attr.name, #struct_name, #expected_attributes,
+        ));
+      }
+    }
+  };
+
+  let deny_unknown_text = if fields.iter().any(|field| field.is_text_content()) {
+    quote!()
+  } else {
+    let accepts_text = (!flattened.is_empty()).then(|| {
+      let accepts_text = accepts_text(&fields);
+      quote!(&& !(#accepts_text))
+    });
+    quote! {
+      if (#deny || reader.deny_unknown_fields()) && !flattened_root #accepts_text {
+        return ::std::result::Result::Err(::std::format!(
+          "Unexpected text content {:?} in {}",
+          text_content, #struct_name,
+        ));
+      }
+    }
+  };
+
+  (
+    deny_unknown_element,
+    deny_unknown_attributes,
+    deny_unknown_text,
+  )
+}
+
+/// Fail when an `alias` of a field matches the name or an alias of another field
+fn check_field_aliases(data_struct: &DataStruct, root_attributes: &YaSerdeAttribute) {
+  let fields: Vec<YaSerdeField> = data_struct
+    .fields
+    .iter()
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| {
+      !field.is_flatten() && !field.is_text_content() && !field.is_skip_deserializing()
+    })
+    .collect();
+
+  let names_of = |field: &YaSerdeField| -> Vec<(bool, Option<String>, String)> {
+    if field.is_attribute() {
+      field
+        .attribute_names(root_attributes)
+        .into_iter()
+        .map(|(namespace, name)| {
+          let namespace = namespace.map(|namespace| namespace.key().to_string());
+          (true, namespace, name)
+        })
+        .collect()
+    } else {
+      std::iter::once((
+        field.prefix_namespace(root_attributes),
+        field.renamed_label_without_namespace(),
+      ))
+      .chain(field.aliases(root_attributes))
+      .map(|(namespace, name)| (false, Some(namespace.key().to_string()), name))
+      .collect()
+    }
+  };
+  let label = |field: &YaSerdeField| field.label().map(|label| label.to_string());
+
+  let mut names = BTreeMap::new();
+  for field in &fields {
+    names.insert(names_of(field).remove(0), label(field));
+  }
+
+  for field in &fields {
+    for key in names_of(field).into_iter().skip(1) {
+      let name = key.2.clone();
+      if let Some(other) = names.insert(key, label(field)) {
+        if other != label(field) {
+          panic!(
+            "YaSerDe derive error: alias {:?} of field {} collides with field {}",
+            name,
+            label(field).unwrap_or_default(),
+            other.unwrap_or_default()
+          );
+        }
+      }
     }
   }
 }
 
+/// Namespace and local name of the `name` of an attribute, matched by
+/// `YaSerdeField::attribute_pattern`
+fn attribute_name(name: TokenStream) -> TokenStream {
+  quote! {
+    (
+      reader.__resolve_namespace(&#name, undeclared_prefixes).as_deref(),
+      #name.local_name.as_str(),
+    )
+  }
+}
+
+/// A struct visitor reads the value with the config of the reader.
+fn new_struct_visitor(visitor_label: &Ident) -> TokenStream {
+  quote!(#visitor_label { config: reader.config() })
+}
+
 fn build_call_visitor(
   field_type: &TokenStream,
   visitor: &Ident,
@@ -494,14 +1109,14 @@ fn build_call_visitor(
 
   let namespaces_matching = field.get_namespace_matching(
     root_attributes,
-    quote!(name.namespace.as_ref()),
+    quote!(reader.__resolve_namespace(name, undeclared_prefixes)),
     quote!(name.local_name.as_str()),
   );
 
-  let namespace = field.prefix_namespace(root_attributes);
+  let pattern = field.element_pattern(root_attributes);
 
   Some(quote! {
-    (#namespace, #label_name) => {
+    #pattern => {
       let visitor = #visitor_label{};
 
       #namespaces_matching
@@ -523,6 +1138,78 @@ fn build_call_visitor(
   })
 }
 
+/// Read the items of a list through its wrapper element, unknown children of the wrapper are
+/// skipped unless unknown fields are denied
+fn build_wrapper_visitor(
+  field: &YaSerdeField,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+  item_visitor: TokenStream,
+) -> TokenStream {
+  let (wrapper_namespace, wrapper_name) = match field.wrapper_namespace_and_name(root_attributes) {
+    Some(wrapper) => wrapper,
+    None => return item_visitor,
+  };
+
+  let struct_name = name.to_string();
+  let deny = root_attributes.deny_unknown_fields;
+  let expected_items = std::iter::once(field.renamed_label_without_namespace())
+    .chain(
+      field
+        .aliases(root_attributes)
+        .into_iter()
+        .map(|(_namespace, name)| name),
+    )
+    .collect::<Vec<_>>()
+    .join(", ");
+
+  quote! {
+    (#wrapper_namespace, #wrapper_name) => {
+      let _wrapper = reader.next_event()?;
+      let wrapper_depth = reader.depth();
+
+      loop {
+        match reader.peek()?.to_owned() {
+          ::yaserde::__xml::reader::XmlEvent::StartElement { ref name, .. } => {
+            let namespace = reader
+              .__resolve_namespace(name, undeclared_prefixes)
+              .unwrap_or_default();
+
+            match (namespace.as_str(), name.local_name.as_str()) {
+              #item_visitor
+              _ => {
+                if #deny || reader.deny_unknown_fields() {
+                  return ::std::result::Result::Err(::std::format!(
+                    "Unexpected element <{}> in {} of {}, expected one of: {}",
+                    name.local_name, #wrapper_name, #struct_name, #expected_items,
+                  ));
+                }
+                let _event = reader.next_event()?;
+                reader.skip_element(|_event| {})?;
+              }
+            }
+          }
+          ::yaserde::__xml::reader::XmlEvent::EndElement { .. }
+            if reader.depth() == wrapper_depth =>
+          {
+            let _event = reader.next_event()?;
+            break;
+          }
+          ::yaserde::__xml::reader::XmlEvent::EndDocument => {
+            return ::std::result::Result::Err(::std::format!(
+              "unexpected end of document in {}",
+              #wrapper_name
+            ));
+          }
+          _ => {
+            let _event = reader.next_event()?;
+          }
+        }
+      }
+    }
+  }
+}
+
 fn build_code_for_unused_xml_events(
   call_flatten_visitors: &TokenStream,
 ) -> (
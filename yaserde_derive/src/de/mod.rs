@@ -15,17 +15,7 @@ pub fn expand_derive_deserialize(ast: &syn::DeriveInput) -> Result<TokenStream,
   let root_attributes = YaSerdeAttribute::from(attrs);
 
   let root_name = root_attributes.xml_element_name(name);
-  let root_namespace = root_attributes
-    .namespaces
-    .iter()
-    .find_map(|(prefix, namespace)| {
-      if root_attributes.prefix.as_deref().eq(&Some(prefix)) {
-        Some(namespace.clone())
-      } else {
-        None
-      }
-    })
-    .unwrap_or_default();
+  let root_namespace = root_attributes.container_namespace_uri();
 
   let impl_block = match *data {
     syn::Data::Struct(ref data_struct) => expand_struct::parse(
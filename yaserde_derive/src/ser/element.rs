@@ -2,10 +2,6 @@ use crate::common::YaSerdeField;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
-pub fn enclose_formatted_characters(label: &Ident, label_name: String) -> TokenStream {
-  enclose_xml_event(label_name, quote!(format!("{}", &self.#label)))
-}
-
 pub fn enclose_formatted_characters_for_value(label: &Ident, label_name: String) -> TokenStream {
   enclose_xml_event(label_name, quote!(format!("{}", #label)))
 }
@@ -14,7 +10,7 @@ pub fn enclose_characters(label: &Option<Ident>, label_name: String) -> TokenStr
   enclose_xml_event(label_name, quote!(format!("{}", self.#label)))
 }
 
-fn enclose_xml_event(label_name: String, yaserde_format: TokenStream) -> TokenStream {
+pub fn enclose_xml_event(label_name: String, yaserde_format: TokenStream) -> TokenStream {
   quote! {
     let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#label_name);
     writer.write(start_event).map_err(|e| e.to_string())?;
@@ -13,7 +13,15 @@ pub fn serialize(
   root_attributes: &YaSerdeAttribute,
   generics: &Generics,
 ) -> TokenStream {
+  if root_attributes.untagged {
+    return serialize_untagged(data_enum, name, root, root_attributes, generics);
+  }
+  if root_attributes.is_adjacently_tagged() {
+    return serialize_adjacently_tagged(data_enum, name, root, root_attributes, generics);
+  }
+
   let inner_enum_inspector = inner_enum_inspector(data_enum, name, root_attributes);
+  let other_value = other_value_variant(data_enum);
 
   let get_id = |field: &YaSerdeField| {
     field
@@ -26,14 +34,19 @@ pub fn serialize(
     .iter()
     .map(|variant| -> TokenStream {
       let add_tag = if let Some(tag) = &root_attributes.tag {
-        let attrs = crate::common::YaSerdeAttribute::from(&variant.attrs);
+        let attrs = root_attributes.variant_attributes(variant);
         let label = variant.ident.clone();
-        let element_name = attrs.xml_element_name(&variant.ident);
+        let (pattern, element_name) = if other_value == Some(&label) {
+          (quote!((ref value)), quote!(value.as_str()))
+        } else {
+          let element_name = attrs.xml_element_name(&variant.ident);
+          (quote!({ .. }), quote!(#element_name))
+        };
 
         quote! {
           match self {
-            #name::#label { .. } => {
-              let tag = ::yaserde::__xml::name::OwnedName::local(#tag);
+            #name::#label #pattern => {
+              let tag = ::yaserde::__xml::name::Name::from(#tag).to_owned();
               child_attributes.push(::yaserde::__xml::attribute::OwnedAttribute::new(tag, #element_name));
             }
             _ => {}
@@ -108,6 +121,318 @@ pub fn serialize(
     quote!(match self {
       #inner_enum_inspector
     }),
+    value_serializer(data_enum, name, root_attributes),
+    generics,
+  )
+}
+
+/// The `other` variant keeping unknown values, written back as they were read
+fn other_value_variant(data_enum: &DataEnum) -> Option<&Ident> {
+  YaSerdeAttribute::other_variant(data_enum)
+    .filter(|(_, keeps_value)| *keeps_value)
+    .map(|(label, _)| label)
+}
+
+fn value_serializer(
+  data_enum: &DataEnum,
+  name: &Ident,
+  root_attributes: &YaSerdeAttribute,
+) -> TokenStream {
+  if !root_attributes.is_value_enum(data_enum) {
+    return quote!();
+  }
+
+  let element_names: TokenStream = data_enum
+    .variants
+    .iter()
+    .map(|variant| {
+      let variant_attrs = root_attributes.variant_attributes(variant);
+      let label = &variant.ident;
+      let label_name = build_label_name(label, &variant_attrs, &root_attributes.default_namespace);
+
+      match variant.fields {
+        Fields::Unit => quote! { #name::#label => #label_name, },
+        _ => quote! { #name::#label(ref value) => value.as_str(), },
+      }
+    })
+    .collect();
+
+  quote! {
+    fn serialize_value(
+      &self,
+    ) -> ::std::option::Option<::std::result::Result<::std::string::String, ::std::string::String>> {
+      let value = match self {
+        #element_names
+      };
+      ::std::option::Option::Some(::std::result::Result::Ok(value.to_string()))
+    }
+  }
+}
+
+fn serialize_untagged(
+  data_enum: &DataEnum,
+  name: &Ident,
+  root: &str,
+  root_attributes: &YaSerdeAttribute,
+  generics: &Generics,
+) -> TokenStream {
+  if let Some(errors) = YaSerdeAttribute::check_single_value_variants(data_enum, "untagged") {
+    return errors;
+  }
+  YaSerdeAttribute::check_untagged_other(data_enum);
+
+  let variant_fields = |variant: &syn::Variant| match variant.fields {
+    Fields::Unit => None,
+    Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+      Some(YaSerdeField::new(fields.unnamed[0].clone()).get_type())
+    }
+    Fields::Unnamed(_) | Fields::Named(_) => {
+      unreachable!("variants with several or named fields are rejected")
+    }
+  };
+
+  let add_attributes: TokenStream = data_enum
+    .variants
+    .iter()
+    .map(|variant| {
+      let label = &variant.ident;
+
+      let item = match variant_fields(variant) {
+        Some(Field::FieldStruct { .. }) => quote!(item),
+        Some(Field::FieldOption { data_type })
+          if matches!(*data_type, Field::FieldStruct { .. }) =>
+        {
+          quote!(::std::option::Option::Some(item))
+        }
+        _ => return quote!(),
+      };
+
+      quote! {
+        if let #name::#label(#item) = self {
+          let (attributes, namespace) = ::yaserde::YaSerialize::serialize_attributes(
+            item,
+            ::std::vec::Vec::new(),
+            ::yaserde::__xml::namespace::Namespace::empty(),
+          )?;
+          child_attributes_namespace.extend(&namespace);
+          child_attributes.extend(attributes);
+        }
+      }
+    })
+    .collect();
+
+  let inner_inspector: TokenStream = data_enum
+    .variants
+    .iter()
+    .map(|variant| {
+      let variant_attrs = root_attributes.variant_attributes(variant);
+      let label = &variant.ident;
+      let label_name = build_label_name(label, &variant_attrs, &root_attributes.default_namespace);
+
+      let write_content = |data_type: Field| match data_type {
+        Field::FieldStruct { .. } => quote! {
+          writer.set_start_event_name(::std::option::Option::None);
+          writer.set_skip_start_end(true);
+          ::yaserde::YaSerialize::serialize(item, writer)?;
+        },
+        Field::FieldOption { .. } | Field::FieldVec { .. } => {
+          unreachable!("nested Option or Vec are rejected")
+        }
+        _simple_type => quote! {
+          let s = item.to_string();
+          let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
+          writer.write(data_event).map_err(|e| e.to_string())?;
+        },
+      };
+
+      match variant_fields(variant) {
+        None => quote! {
+          #name::#label => {
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(#label_name);
+            writer.write(data_event).map_err(|e| e.to_string())?;
+          }
+        },
+        Some(Field::FieldOption { data_type }) => {
+          let write = write_content(*data_type);
+
+          quote! {
+            #name::#label(ref item) => {
+              if let ::std::option::Option::Some(item) = item {
+                #write
+              }
+            }
+          }
+        }
+        Some(Field::FieldVec { .. }) => unreachable!("Vec variants are rejected"),
+        Some(data_type) => {
+          let write = write_content(data_type);
+
+          quote! {
+            #name::#label(ref item) => {
+              #write
+            }
+          }
+        }
+      }
+    })
+    .collect();
+
+  implement_serializer(
+    name,
+    root,
+    root_attributes,
+    add_attributes,
+    quote!(match self {
+      #inner_inspector
+    }),
+    quote!(),
+    generics,
+  )
+}
+
+fn serialize_adjacently_tagged(
+  data_enum: &DataEnum,
+  name: &Ident,
+  root: &str,
+  root_attributes: &YaSerdeAttribute,
+  generics: &Generics,
+) -> TokenStream {
+  if let Some(errors) =
+    YaSerdeAttribute::check_single_value_variants(data_enum, "adjacently tagged")
+  {
+    return errors;
+  }
+
+  let tag = root_attributes.tag.clone().unwrap_or_default();
+  let content_name = root_attributes.content.clone().unwrap_or_default();
+  let tag_is_element = root_attributes.is_tag_element();
+  let other_value = other_value_variant(data_enum);
+
+  let add_tag: TokenStream = if tag_is_element {
+    quote!()
+  } else {
+    let element_names: TokenStream = data_enum
+      .variants
+      .iter()
+      .map(|variant| {
+        let label = &variant.ident;
+        let element_name = root_attributes
+          .variant_attributes(variant)
+          .xml_element_name(label);
+
+        if other_value == Some(label) {
+          quote! { #name::#label(ref value) => value.as_str(), }
+        } else {
+          quote! { #name::#label { .. } => #element_name, }
+        }
+      })
+      .collect();
+
+    quote! {
+      let tag = ::yaserde::__xml::name::Name::from(#tag).to_owned();
+      let element_name = match self {
+        #element_names
+      };
+      child_attributes.push(::yaserde::__xml::attribute::OwnedAttribute::new(tag, element_name));
+    }
+  };
+
+  let inner_inspector: TokenStream = data_enum
+    .variants
+    .iter()
+    .map(|variant| {
+      let label = &variant.ident;
+      let keeps_value = other_value == Some(label);
+      let element_name = if keeps_value {
+        quote!(value.as_str())
+      } else {
+        let element_name = root_attributes
+          .variant_attributes(variant)
+          .xml_element_name(label);
+        quote!(#element_name)
+      };
+
+      let write_tag = if tag_is_element {
+        quote! {
+          let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#tag);
+          writer.write(start_event).map_err(|e| e.to_string())?;
+          let data_event = ::yaserde::__xml::writer::XmlEvent::characters(#element_name);
+          writer.write(data_event).map_err(|e| e.to_string())?;
+          let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+          writer.write(end_event).map_err(|e| e.to_string())?;
+        }
+      } else {
+        quote!()
+      };
+
+      let write_content = |data_type: Field| match data_type {
+        Field::FieldStruct { .. } => quote! {
+          writer.set_start_event_name(::std::option::Option::Some(#content_name.to_string()));
+          writer.set_skip_start_end(false);
+          ::yaserde::YaSerialize::serialize(item, writer)?;
+        },
+        Field::FieldOption { .. } | Field::FieldVec { .. } => {
+          unreachable!("nested Option or Vec are rejected")
+        }
+        _simple_type => quote! {
+          let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#content_name);
+          writer.write(start_event).map_err(|e| e.to_string())?;
+          let s = item.to_string();
+          let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
+          writer.write(data_event).map_err(|e| e.to_string())?;
+          let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+          writer.write(end_event).map_err(|e| e.to_string())?;
+        },
+      };
+
+      match variant.fields {
+        Fields::Unit => quote! {
+          #name::#label => {
+            #write_tag
+          }
+        },
+        _ if keeps_value => quote! {
+          #name::#label(ref value) => {
+            #write_tag
+          }
+        },
+        Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+          let write = match YaSerdeField::new(fields.unnamed[0].clone()).get_type() {
+            Field::FieldOption { data_type } => {
+              let write = write_content(*data_type);
+              quote! {
+                if let ::std::option::Option::Some(item) = item {
+                  #write
+                }
+              }
+            }
+            Field::FieldVec { .. } => unreachable!("Vec variants are rejected"),
+            data_type => write_content(data_type),
+          };
+
+          quote! {
+            #name::#label(ref item) => {
+              #write_tag
+              #write
+            }
+          }
+        }
+        Fields::Unnamed(_) | Fields::Named(_) => {
+          unreachable!("variants with several or named fields are rejected")
+        }
+      }
+    })
+    .collect();
+
+  implement_serializer(
+    name,
+    root,
+    root_attributes,
+    add_tag,
+    quote!(match self {
+      #inner_inspector
+    }),
+    quote!(),
     generics,
   )
 }
@@ -117,11 +442,13 @@ fn inner_enum_inspector(
   name: &Ident,
   root_attributes: &YaSerdeAttribute,
 ) -> TokenStream {
+  let other_value = other_value_variant(data_enum);
+
   data_enum
     .variants
     .iter()
     .map(|variant| {
-      let variant_attrs = YaSerdeAttribute::from(&variant.attrs);
+      let variant_attrs = root_attributes.variant_attributes(variant);
 
       let label = &variant.ident;
       let label_name = build_label_name(label, &variant_attrs, &root_attributes.default_namespace);
@@ -158,36 +485,6 @@ fn inner_enum_inspector(
               let field_label_name = field.renamed_label(root_attributes);
 
               match field.get_type() {
-                Field::FieldString
-                | Field::FieldBool
-                | Field::FieldU8
-                | Field::FieldI8
-                | Field::FieldU16
-                | Field::FieldI16
-                | Field::FieldU32
-                | Field::FieldI32
-                | Field::FieldF32
-                | Field::FieldU64
-                | Field::FieldI64
-                | Field::FieldF64 => Some({
-                  quote! {
-                    match self {
-                      &#name::#label { ref #field_label, .. } => {
-                        let struct_start_event =
-                          ::yaserde::__xml::writer::XmlEvent::start_element(#field_label_name);
-                        writer.write(struct_start_event).map_err(|e| e.to_string())?;
-
-                        let string_value = #field_label.to_string();
-                        let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&string_value);
-                        writer.write(data_event).map_err(|e| e.to_string())?;
-
-                        let struct_end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
-                        writer.write(struct_end_event).map_err(|e| e.to_string())?;
-                      },
-                      _ => {},
-                    }
-                  }
-                }),
                 Field::FieldStruct { .. } => Some(quote! {
                   match self {
                     &#name::#label{ref #field_label, ..} => {
@@ -215,6 +512,25 @@ fn inner_enum_inspector(
                   }
                 }),
                 Field::FieldOption { .. } => None,
+                _simple_type => Some({
+                  quote! {
+                    match self {
+                      &#name::#label { ref #field_label, .. } => {
+                        let struct_start_event =
+                          ::yaserde::__xml::writer::XmlEvent::start_element(#field_label_name);
+                        writer.write(struct_start_event).map_err(|e| e.to_string())?;
+
+                        let string_value = #field_label.to_string();
+                        let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&string_value);
+                        writer.write(data_event).map_err(|e| e.to_string())?;
+
+                        let struct_end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+                        writer.write(struct_end_event).map_err(|e| e.to_string())?;
+                      },
+                      _ => {},
+                    }
+                  }
+                }),
               }
             })
             .collect();
@@ -225,6 +541,22 @@ fn inner_enum_inspector(
             }
           }
         }
+        Fields::Unnamed(_) if other_value == Some(label) => {
+          if root_attributes.tag.is_some() {
+            quote! { #name::#label(_) => {} }
+          } else {
+            quote! {
+              #name::#label(ref value) => {
+                if value.starts_with('<') {
+                  writer.write_element_string(value)?;
+                } else {
+                  let data_event = ::yaserde::__xml::writer::XmlEvent::characters(value);
+                  writer.write(data_event).map_err(|e| e.to_string())?;
+                }
+              }
+            }
+          }
+        }
         Fields::Unnamed(ref fields) => {
           let enum_fields: TokenStream = fields
             .unnamed
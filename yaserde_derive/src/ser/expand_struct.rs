@@ -13,79 +13,120 @@ pub fn serialize(
   root_attributes: &YaSerdeAttribute,
   generics: &Generics,
 ) -> TokenStream {
+  // The default namespace does not apply to attributes, its prefix is declared when they use it
+  let default_prefix_declaration = root_attributes
+    .default_namespace
+    .as_ref()
+    .filter(|default_prefix| {
+      data_struct
+        .fields
+        .iter()
+        .map(|field| YaSerdeField::new(field.clone()))
+        .any(|field| {
+          field.is_attribute()
+            && !field.is_skip_serializing()
+            && field.get_prefix().as_ref() == Some(*default_prefix)
+        })
+    })
+    .and_then(|prefix| {
+      root_attributes.namespace_uri(prefix).map(
+        |namespace| quote!(let struct_start_event = struct_start_event.ns(#prefix, #namespace);),
+      )
+    });
+
   let append_attributes: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
     .filter(|field| field.is_attribute() || field.is_flatten())
+    .filter(|field| !field.is_skip_serializing())
     .map(|field| {
       let label = field.label();
 
       if field.is_attribute() {
-        let label_name = field.renamed_label(root_attributes);
+        let label_name = field.renamed_attribute_label();
 
-        match field.get_type() {
-          Field::FieldString
-          | Field::FieldBool
-          | Field::FieldI8
-          | Field::FieldU8
-          | Field::FieldI16
-          | Field::FieldU16
-          | Field::FieldI32
-          | Field::FieldU32
-          | Field::FieldI64
-          | Field::FieldU64
-          | Field::FieldF32
-          | Field::FieldF64 => field.ser_wrap_default_attribute(
-            Some(quote!(self.#label.to_string())),
-            quote!({
-              struct_start_event.attr(#label_name, &yaserde_inner)
-            }),
-          ),
-          Field::FieldOption { data_type } => match *data_type {
-            Field::FieldString => field.ser_wrap_default_attribute(
-              None,
+        if let Some(serialize_with) = field.get_serialize_with() {
+          return if field.is_option() {
+            field.ser_wrap_default_attribute(
+              Some(quote! {
+                self.#label
+                  .as_ref()
+                  .map(|yaserde_value| #serialize_with(yaserde_value))
+                  .transpose()?
+              }),
               quote!({
-                if let ::std::option::Option::Some(ref value) = self.#label {
-                  struct_start_event.attr(#label_name, value)
+                if let ::std::option::Option::Some(ref yaserde_value) = yaserde_inner {
+                  struct_start_event.attr(#label_name, yaserde_value)
                 } else {
                   struct_start_event
                 }
               }),
+            )
+          } else {
+            field.ser_wrap_default_attribute(
+              Some(quote!(#serialize_with(&self.#label)?)),
+              quote!({
+                struct_start_event.attr(#label_name, &yaserde_inner)
+              }),
+            )
+          };
+        }
+
+        if field.is_binary() {
+          return match field.get_type() {
+            Field::FieldOption { .. } => {
+              let encoded = field.encode_binary(quote!(yaserde_bytes));
+
+              field.ser_wrap_default_attribute(
+                Some(quote!(self.#label.as_ref().map(|yaserde_bytes| #encoded))),
+                quote!({
+                  if let ::std::option::Option::Some(ref yaserde_encoded) = yaserde_inner {
+                    struct_start_event.attr(#label_name, yaserde_encoded)
+                  } else {
+                    struct_start_event
+                  }
+                }),
+              )
+            }
+            _ => field.ser_wrap_default_attribute(
+              Some(field.encode_binary(quote!(&self.#label))),
+              quote!({
+                struct_start_event.attr(#label_name, &yaserde_inner)
+              }),
             ),
-            Field::FieldBool
-            | Field::FieldI8
-            | Field::FieldU8
-            | Field::FieldI16
-            | Field::FieldU16
-            | Field::FieldI32
-            | Field::FieldU32
-            | Field::FieldI64
-            | Field::FieldU64
-            | Field::FieldF32
-            | Field::FieldF64 => field.ser_wrap_default_attribute(
-              Some(
-                quote!(self.#label.map_or_else(|| ::std::string::String::new(), |v| v.to_string())),
-              ),
+          };
+        }
+
+        match field.get_type() {
+          Field::FieldOption { data_type } => match *data_type {
+            Field::FieldString => field.ser_wrap_default_attribute(
+              None,
               quote!({
                 if let ::std::option::Option::Some(ref value) = self.#label {
-                  struct_start_event.attr(#label_name, &yaserde_inner)
+                  struct_start_event.attr(#label_name, value)
                 } else {
                   struct_start_event
                 }
               }),
             ),
-            Field::FieldVec { .. } => {
-              let item_ident = Ident::new("yaserde_item", field.get_span());
-              let inner = enclose_formatted_characters(&item_ident, label_name);
+            Field::FieldVec { data_type } => {
+              let list = build_list(&field, &data_type, quote!(yaserde_list));
 
               field.ser_wrap_default_attribute(
-                None,
+                Some(quote! {
+                  self.#label
+                    .as_ref()
+                    .map_or_else(
+                      || ::std::result::Result::Ok(::std::string::String::new()),
+                      |yaserde_list| ::std::result::Result::Ok::<_, ::std::string::String>(#list),
+                    )?
+                }),
                 quote!({
-                  if let ::std::option::Option::Some(ref yaserde_list) = self.#label {
-                    for yaserde_item in yaserde_list.iter() {
-                      #inner
-                    }
+                  if yaserde_inner.is_empty() {
+                    struct_start_event
+                  } else {
+                    struct_start_event.attr(#label_name, &yaserde_inner)
                   }
                 }),
               )
@@ -96,7 +137,7 @@ pub fn serialize(
                 .as_ref()
                 .map_or_else(
                   || ::std::result::Result::Ok(::std::string::String::new()),
-                  |v| ::yaserde::ser::to_string_content(v),
+                  |v| ::yaserde::ser::to_string_value(v),
                 )?
               }),
               quote!({
@@ -108,17 +149,45 @@ pub fn serialize(
               }),
             ),
             Field::FieldOption { .. } => unimplemented!(),
+            _simple_type => field.ser_wrap_default_attribute(
+              Some(
+                quote!(self.#label.map_or_else(|| ::std::string::String::new(), |v| v.to_string())),
+              ),
+              quote!({
+                if let ::std::option::Option::Some(ref value) = self.#label {
+                  struct_start_event.attr(#label_name, &yaserde_inner)
+                } else {
+                  struct_start_event
+                }
+              }),
+            ),
           },
           Field::FieldStruct { .. } => field.ser_wrap_default_attribute(
-            Some(quote! { ::yaserde::ser::to_string_content(&self.#label)? }),
+            Some(quote! { ::yaserde::ser::to_string_value(&self.#label)? }),
             quote!({
               struct_start_event.attr(#label_name, &yaserde_inner)
             }),
           ),
-          Field::FieldVec { .. } => {
-            // TODO
-            quote!()
+          Field::FieldVec { data_type } => {
+            let list = build_list(&field, &data_type, quote!(self.#label));
+
+            field.ser_wrap_default_attribute(
+              Some(list),
+              quote!({
+                if yaserde_inner.is_empty() {
+                  struct_start_event
+                } else {
+                  struct_start_event.attr(#label_name, &yaserde_inner)
+                }
+              }),
+            )
           }
+          _simple_type => field.ser_wrap_default_attribute(
+            Some(quote!(self.#label.to_string())),
+            quote!({
+              struct_start_event.attr(#label_name, &yaserde_inner)
+            }),
+          ),
         }
       } else {
         match field.get_type() {
@@ -141,19 +210,126 @@ pub fn serialize(
   let struct_inspector: TokenStream = data_struct
     .fields
     .iter()
-    .map(|field| YaSerdeField::new(field.clone()))
-    .filter(|field| !field.is_attribute())
+    .map(|field| YaSerdeField::new(field.clone()).with_container(root_attributes))
+    .filter(|field| !field.is_attribute() && !field.is_skip_serializing())
     .filter_map(|field| {
       let label = field.label();
+      if let Some(serialize_with) = field.get_serialize_with() {
+        let write_value = if field.is_text_content() {
+          quote! {
+            let yaserde_value = #serialize_with(yaserde_value)?;
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&yaserde_value);
+            writer.write(data_event).map_err(|e| e.to_string())?;
+          }
+        } else {
+          let label_name = field.renamed_label(root_attributes);
+          quote! {
+            let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#label_name);
+            writer.write(start_event).map_err(|e| e.to_string())?;
+
+            #serialize_with(yaserde_value, writer)?;
+
+            let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+            writer.write(end_event).map_err(|e| e.to_string())?;
+          }
+        };
+        let write_value = if field.is_option() {
+          quote! {
+            if let ::std::option::Option::Some(ref yaserde_value) = self.#label {
+              #write_value
+            }
+          }
+        } else {
+          quote! {
+            let yaserde_value = &self.#label;
+            #write_value
+          }
+        };
+        let conditions = condition_generator(&label, &field);
+
+        return Some(quote! {
+          #conditions {
+            #write_value
+          }
+        });
+      }
+      if field.is_binary() {
+        let encoded = field.encode_binary(quote!(yaserde_bytes));
+        let write_value = if field.is_text_content() {
+          quote! {
+            let yaserde_value = #encoded;
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&yaserde_value);
+            writer.write(data_event).map_err(|e| e.to_string())?;
+          }
+        } else {
+          enclose_xml_event(field.renamed_label(root_attributes), encoded)
+        };
+        let write_value = match field.get_type() {
+          Field::FieldOption { .. } => quote! {
+            if let ::std::option::Option::Some(ref yaserde_bytes) = self.#label {
+              #write_value
+            }
+          },
+          _ => quote! {
+            let yaserde_bytes = &self.#label;
+            #write_value
+          },
+        };
+        let conditions = condition_generator(&label, &field);
+
+        return Some(quote! {
+          #conditions {
+            #write_value
+          }
+        });
+      }
       if field.is_text_content() {
         return match field.get_type() {
-          Field::FieldOption { .. } => Some(quote!(
-            let s = self.#label.as_deref().unwrap_or_default();
-            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(s);
+          Field::FieldOption { data_type } => match *data_type {
+            Field::FieldString => Some(quote!(
+              let s = self.#label.as_deref().unwrap_or_default();
+              let data_event = ::yaserde::__xml::writer::XmlEvent::characters(s);
+              writer.write(data_event).map_err(|e| e.to_string())?;
+            )),
+            Field::FieldStruct { .. } => Some(quote!(
+              if let ::std::option::Option::Some(ref value) = self.#label {
+                let s = ::yaserde::ser::to_string_value(value)?;
+                let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
+                writer.write(data_event).map_err(|e| e.to_string())?;
+              }
+            )),
+            Field::FieldOption { .. } | Field::FieldVec { .. } => unimplemented!(),
+            _simple_type => Some(quote!(
+              if let ::std::option::Option::Some(ref value) = self.#label {
+                let s = value.to_string();
+                let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
+                writer.write(data_event).map_err(|e| e.to_string())?;
+              }
+            )),
+          },
+          Field::FieldVec { data_type } => {
+            let list = build_list(&field, &data_type, quote!(self.#label));
+
+            Some(quote!(
+              let yaserde_list = #list;
+              if !yaserde_list.is_empty() {
+                let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&yaserde_list);
+                writer.write(data_event).map_err(|e| e.to_string())?;
+              }
+            ))
+          }
+          Field::FieldString => Some(quote!(
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&self.#label);
             writer.write(data_event).map_err(|e| e.to_string())?;
           )),
-          _ => Some(quote!(
-            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&self.#label);
+          Field::FieldStruct { .. } => Some(quote!(
+            let s = ::yaserde::ser::to_string_value(&self.#label)?;
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
+            writer.write(data_event).map_err(|e| e.to_string())?;
+          )),
+          _simple_type => Some(quote!(
+            let s = self.#label.to_string();
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&s);
             writer.write(data_event).map_err(|e| e.to_string())?;
           )),
         };
@@ -174,33 +350,38 @@ pub fn serialize(
         }.into()
       }
 
-      match field.get_type() {
-        Field::FieldString
-        | Field::FieldBool
-        | Field::FieldI8
-        | Field::FieldU8
-        | Field::FieldI16
-        | Field::FieldU16
-        | Field::FieldI32
-        | Field::FieldU32
-        | Field::FieldI64
-        | Field::FieldU64
-        | Field::FieldF32
-        | Field::FieldF64 => serialize_element(&label, label_name, &conditions),
+      let enclose_list = |data_type: &Field, items: TokenStream| {
+        let list = build_list(&field, data_type, items);
+
+        quote! {
+          let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#label_name);
+          writer.write(start_event).map_err(|e| e.to_string())?;
+
+          let yaserde_list = #list;
+          if !yaserde_list.is_empty() {
+            let data_event = ::yaserde::__xml::writer::XmlEvent::characters(&yaserde_list);
+            writer.write(data_event).map_err(|e| e.to_string())?;
+          }
+
+          let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+          writer.write(end_event).map_err(|e| e.to_string())?;
+        }
+      };
 
+      match field.get_type() {
         Field::FieldOption { data_type } => match *data_type {
-          Field::FieldString
-          | Field::FieldBool
-          | Field::FieldI8
-          | Field::FieldU8
-          | Field::FieldI16
-          | Field::FieldU16
-          | Field::FieldI32
-          | Field::FieldU32
-          | Field::FieldI64
-          | Field::FieldU64
-          | Field::FieldF32
-          | Field::FieldF64 => {
+          Field::FieldVec { data_type } if field.is_list() => {
+            let inner = enclose_list(&data_type, quote!(yaserde_items));
+
+            Some(quote! {
+              #conditions {
+                if let ::std::option::Option::Some(ref yaserde_items) = &self.#label {
+                  #inner
+                }
+              }
+            })
+          }
+          simple_type if simple_type.is_simple_type() => {
             let item_ident = Ident::new("yaserde_item", field.get_span());
             let inner = enclose_formatted_characters_for_value(&item_ident, label_name);
 
@@ -267,43 +448,18 @@ pub fn serialize(
             }
           })
         }
-        Field::FieldVec { data_type } => match *data_type {
-          Field::FieldString => {
-            let item_ident = Ident::new("yaserde_item", field.get_span());
-            let inner = enclose_formatted_characters_for_value(&item_ident, label_name);
+        Field::FieldVec { data_type } if field.is_list() => {
+          let inner = enclose_list(&data_type, quote!(self.#label));
 
-            Some(quote! {
-              #conditions {
-                for yaserde_item in &self.#label {
-                  #inner
-                }
-              }
-            })
-          }
-          Field::FieldBool
-          | Field::FieldI8
-          | Field::FieldU8
-          | Field::FieldI16
-          | Field::FieldU16
-          | Field::FieldI32
-          | Field::FieldU32
-          | Field::FieldI64
-          | Field::FieldU64
-          | Field::FieldF32
-          | Field::FieldF64 => {
-            let item_ident = Ident::new("yaserde_item", field.get_span());
-            let inner = enclose_formatted_characters_for_value(&item_ident, label_name);
-
-            Some(quote! {
-              #conditions {
-                for yaserde_item in &self.#label {
-                  #inner
-                }
-              }
-            })
-          }
-          Field::FieldOption { .. } => Some(quote! {
+          Some(quote! {
             #conditions {
+              #inner
+            }
+          })
+        }
+        Field::FieldVec { data_type } => {
+          let items = match *data_type {
+            Field::FieldOption { .. } => quote! {
               for item in &self.#label {
                 if let Some(value) = item {
                   writer.set_start_event_name(None);
@@ -311,46 +467,67 @@ pub fn serialize(
                   ::yaserde::YaSerialize::serialize(value, writer)?;
                 }
               }
-            }
-          }),
-          Field::FieldStruct { .. } => {
-            if field.is_flatten() {
-              Some(quote! {
-                #conditions {
+            },
+            Field::FieldStruct { .. } => {
+              if field.is_flatten() {
+                quote! {
                   for item in &self.#label {
-                      writer.set_start_event_name(::std::option::Option::None);
+                    writer.set_start_event_name(::std::option::Option::None);
                     writer.set_skip_start_end(true);
                     ::yaserde::YaSerialize::serialize(item, writer)?;
                   }
                 }
-              })
-            } else {
-              Some(quote! {
-                #conditions {
+              } else {
+                quote! {
                   for item in &self.#label {
                     writer.set_start_event_name(::std::option::Option::Some(#label_name.to_string()));
                     writer.set_skip_start_end(false);
                     ::yaserde::YaSerialize::serialize(item, writer)?;
-                 }
+                  }
                 }
-              })
+              }
+            }
+            Field::FieldVec { .. } => {
+              unimplemented!();
             }
-            /*let (start_event, skip_start) = if field.is_flatten() {
-              (quote!(None), true)
-            } else {
-              (quote!(Some(#label_name.to_string())), false)
-            };
+            _simple_type => {
+              let item_ident = Ident::new("yaserde_item", field.get_span());
+              let inner = enclose_formatted_characters_for_value(&item_ident, label_name);
 
-            Some(quote! {
-              writer.set_start_event_name(#start_event);
-              writer.set_skip_start_end(#skip_start);
-              ::yaserde::YaSerialize::serialize(&self.#label, writer)?;
-            })*/
-          }
-          Field::FieldVec { .. } => {
-            unimplemented!();
-          }
-        },
+              quote! {
+                for yaserde_item in &self.#label {
+                  #inner
+                }
+              }
+            }
+          };
+
+          let items = field
+            .renamed_wrapper_label(root_attributes)
+            .map(|wrapper_name| {
+              let emit_empty_wrapper = field.is_emit_empty_wrapper();
+
+              quote! {
+                if #emit_empty_wrapper || !self.#label.is_empty() {
+                  let start_event = ::yaserde::__xml::writer::XmlEvent::start_element(#wrapper_name);
+                  writer.write(start_event).map_err(|e| e.to_string())?;
+
+                  #items
+
+                  let end_event = ::yaserde::__xml::writer::XmlEvent::end_element();
+                  writer.write(end_event).map_err(|e| e.to_string())?;
+                }
+              }
+            })
+            .unwrap_or(items);
+
+          Some(quote! {
+            #conditions {
+              #items
+            }
+          })
+        }
+        _simple_type => serialize_element(&label, label_name, &conditions),
       }
     })
     .collect();
@@ -359,8 +536,34 @@ pub fn serialize(
     name,
     root,
     root_attributes,
-    append_attributes,
+    quote!(#default_prefix_declaration #append_attributes),
     struct_inspector,
+    quote!(),
     generics,
   )
 }
+
+/// Join the items of a list-valued field into a single `xs:list` value.
+fn build_list(field: &YaSerdeField, data_type: &Field, items: TokenStream) -> TokenStream {
+  let separator = field.get_list_separator();
+
+  match data_type {
+    Field::FieldStruct { .. } => quote! {
+      #items
+        .iter()
+        .map(::yaserde::ser::to_string_value)
+        .collect::<::std::result::Result<::std::vec::Vec<_>, _>>()?
+        .join(#separator)
+    },
+    Field::FieldOption { .. } | Field::FieldVec { .. } => {
+      unimplemented!("Option or Vec nested in list Vec<>")
+    }
+    _simple_type => quote! {
+      #items
+        .iter()
+        .map(|yaserde_item| yaserde_item.to_string())
+        .collect::<::std::vec::Vec<_>>()
+        .join(#separator)
+    },
+  }
+}
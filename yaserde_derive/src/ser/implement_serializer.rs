@@ -11,6 +11,7 @@ pub fn implement_serializer(
   attributes: &YaSerdeAttribute,
   append_attributes: TokenStream,
   inner_inspector: TokenStream,
+  value_serializer: TokenStream,
   generics: &Generics,
 ) -> TokenStream {
   let namespaces_definition = generate_namespaces_definition(attributes);
@@ -33,7 +34,8 @@ pub fn implement_serializer(
 
           let yaserde_label = writer.get_start_event_name().unwrap_or_else(|| #root.to_string());
           let struct_start_event =
-            ::yaserde::__xml::writer::XmlEvent::start_element(yaserde_label.as_ref()) #namespaces_definition;
+            ::yaserde::__xml::writer::XmlEvent::start_element(yaserde_label.as_ref());
+          #namespaces_definition
           #append_attributes
 
           let event: ::yaserde::__xml::writer::events::XmlEvent = struct_start_event.into();
@@ -84,8 +86,8 @@ pub fn implement_serializer(
         let mut child_attributes_namespace = ::yaserde::__xml::namespace::Namespace::empty();
 
         let struct_start_event =
-          ::yaserde::__xml::writer::XmlEvent::start_element("temporary_element_to_generate_attributes")
-          #namespaces_definition;
+          ::yaserde::__xml::writer::XmlEvent::start_element("temporary_element_to_generate_attributes");
+        #namespaces_definition
 
         #append_attributes
         let event: ::yaserde::__xml::writer::events::XmlEvent = struct_start_event.into();
@@ -104,6 +106,8 @@ pub fn implement_serializer(
           unreachable!();
         }
       }
+
+      #value_serializer
     }
   }
 }
@@ -2,8 +2,9 @@ use crate::common::YaSerdeAttribute;
 use proc_macro2::TokenStream;
 use quote::quote;
 
+/// Statements declaring the namespaces on `struct_start_event`
 pub fn generate_namespaces_definition(attributes: &YaSerdeAttribute) -> TokenStream {
-  attributes
+  let declarations: TokenStream = attributes
     .namespaces
     .iter()
     .map(|(prefix, namespace)| {
@@ -17,5 +18,31 @@ pub fn generate_namespaces_definition(attributes: &YaSerdeAttribute) -> TokenStr
         )
       }
     })
-    .collect()
+    .collect();
+
+  let table_declarations = attributes.namespaces_table.as_ref().map(|table| {
+    let default_namespace = match &attributes.default_namespace {
+      Some(prefix) => quote!(::std::option::Option::Some(#prefix)),
+      None => quote!(::std::option::Option::None),
+    };
+    let inline_prefixes = attributes.namespaces.keys();
+
+    quote!(
+      let struct_start_event = #table::NAMESPACES
+        .iter()
+        .filter(|(prefix, _namespace)| ![#(#inline_prefixes),*].contains(prefix))
+        .fold(struct_start_event, |struct_start_event, (prefix, namespace)| {
+          if #default_namespace == ::std::option::Option::Some(*prefix) {
+            struct_start_event.default_ns(*namespace)
+          } else {
+            struct_start_event.ns(*prefix, *namespace)
+          }
+        });
+    )
+  });
+
+  quote!(
+    let struct_start_event = struct_start_event #declarations;
+    #table_declarations
+  )
 }